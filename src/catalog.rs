@@ -0,0 +1,291 @@
+//! Streaming reader for multi-TLE catalog files.
+//!
+//! Catalog files from CelesTrak, Space-Track and similar providers contain many element sets in
+//! sequence, either as bare two-line records or as three-line records with a name header. Space-Track
+//! prefixes the header with `0 `, which is stripped from the reported name.
+
+use std::io::BufRead;
+
+use crate::{Error, ParseMode, PropagationOptions, Result, TleParseError, TwoLineElement};
+
+/// A TwoLineElement together with the name header that preceded it in a catalog, if any.
+#[derive(Debug, Clone)]
+pub struct NamedTle {
+    pub name: Option<String>,
+    pub tle: TwoLineElement,
+}
+
+/// An iterator over the element sets in a TLE catalog.
+///
+/// Each record may use either the 2-line or the 3-line layout, and the two may be mixed within a
+/// single file. A malformed record produces an `Err` carrying the line number at which the record
+/// starts, after which reading resumes with the next record. Bytes which are not valid UTF-8 are
+/// replaced, so they only affect the record they appear in; reading stops at an I/O error.
+pub struct CatalogReader<R> {
+    reader: R,
+    mode: ParseMode,
+    options: PropagationOptions,
+    line_number: usize,
+    pending: Option<(usize, String)>,
+    done: bool,
+}
+
+impl<R: BufRead> CatalogReader<R> {
+    /// Read a catalog the way [TwoLineElement::new] reads each record.
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseMode::Lenient, PropagationOptions::default())
+    }
+
+    /// Read a catalog, checking each record with the given mode and propagating it with the given
+    /// options, as [TwoLineElement::parse] does.
+    pub fn with_options(reader: R, mode: ParseMode, options: PropagationOptions) -> Self {
+        Self {
+            reader,
+            mode,
+            options,
+            line_number: 0,
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Read the next non-blank line, along with its 1-based line number.
+    fn next_line(&mut self) -> Result<Option<(usize, String)>> {
+        if let Some(pending) = self.pending.take() {
+            return Ok(Some(pending));
+        }
+
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = self
+                .reader
+                .read_until(b'\n', &mut buf)
+                .map_err(|e| Error::IoError(e.to_string()))?;
+            if read == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+
+            let text = String::from_utf8_lossy(&buf);
+            let line = text.trim();
+            if !line.is_empty() {
+                return Ok(Some((self.line_number, line.to_owned())));
+            }
+        }
+    }
+
+    /// Return a line to the reader so that it is yielded again by the next call to `next_line`.
+    fn push_back(&mut self, line: (usize, String)) {
+        self.pending = Some(line);
+    }
+
+    fn next_record(&mut self) -> Result<Option<NamedTle>> {
+        let (start, first) = match self.next_line()? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+
+        let (name, line1) = if is_element_line(&first, '1') {
+            (None, first)
+        } else if is_element_line(&first, '2') {
+            return Err(missing_line(start, 1));
+        } else {
+            let name = first.strip_prefix("0 ").unwrap_or(&first).trim().to_owned();
+            match self.next_line()? {
+                Some((_, line)) if is_element_line(&line, '1') => (Some(name), line),
+                Some(line) => {
+                    self.push_back(line);
+                    return Err(missing_line(start, 1));
+                }
+                None => return Err(missing_line(start, 1)),
+            }
+        };
+
+        let line2 = match self.next_line()? {
+            Some((_, line)) if is_element_line(&line, '2') => line,
+            Some(line) => {
+                self.push_back(line);
+                return Err(missing_line(start, 2));
+            }
+            None => return Err(missing_line(start, 2)),
+        };
+
+        let tle = TwoLineElement::parse(&line1, &line2, self.mode, self.options).map_err(|e| {
+            Error::MalformedCatalogEntry {
+                line: start,
+                error: Box::new(e),
+            }
+        })?;
+
+        Ok(Some(NamedTle { name, tle }))
+    }
+}
+
+impl<R: BufRead> Iterator for CatalogReader<R> {
+    type Item = Result<NamedTle>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_record() {
+            Ok(record) => {
+                self.done = record.is_none();
+                record.map(Ok)
+            }
+            Err(e) => {
+                // An I/O failure is not recoverable, so stop rather than yielding it forever.
+                self.done = matches!(e, Error::IoError(_));
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Determine whether a line looks like the given line of a TLE, as opposed to a name header.
+fn is_element_line(line: &str, line_number: char) -> bool {
+    let mut chars = line.chars();
+    chars.next() == Some(line_number) && chars.next() == Some(' ')
+}
+
+/// Report a record starting at the given line of the file which lacks one of its TLE lines.
+fn missing_line(line: usize, tle_line: usize) -> Error {
+    Error::MalformedCatalogEntry {
+        line,
+        error: Box::new(TleParseError::MissingLine { line: tle_line }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+    use std::io::BufReader;
+
+    const ISS_LINE_1: &str =
+        "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+    const ISS_LINE_2: &str =
+        "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+
+    #[test]
+    fn test_read_three_line_catalog() -> Result<()> {
+        let file = File::open("test_data/spire.txt").unwrap();
+        let records = CatalogReader::new(BufReader::new(file)).collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(records.len(), 87);
+        assert_eq!(records[0].name.as_deref(), Some("LEMUR-1"));
+        assert_eq!(records[1].name.as_deref(), Some("LEMUR-2-JOEL"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_two_line_catalog() -> Result<()> {
+        let catalog = format!("{ISS_LINE_1}\n{ISS_LINE_2}\n\n{ISS_LINE_1}\n{ISS_LINE_2}\n");
+        let records = CatalogReader::new(catalog.as_bytes()).collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| r.name.is_none()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_space_track_name_prefix() -> Result<()> {
+        let catalog =
+            format!("0 ISS (ZARYA)\n{ISS_LINE_1}\n{ISS_LINE_2}\n{ISS_LINE_1}\n{ISS_LINE_2}");
+        let records = CatalogReader::new(catalog.as_bytes()).collect::<Result<Vec<_>>>()?;
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(records[1].name, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        // A bad byte in a name is replaced, and one in an element line spoils only that record.
+        let mut catalog = b"ISS \xff\n".to_vec();
+        catalog.extend(format!("{ISS_LINE_1}\n{ISS_LINE_2}\n").bytes());
+        catalog.extend(ISS_LINE_1[..20].bytes());
+        catalog.push(0xfe);
+        catalog.extend(ISS_LINE_1[21..].bytes());
+        catalog
+            .extend(format!("\n{ISS_LINE_2}\nISS (ZARYA)\n{ISS_LINE_1}\n{ISS_LINE_2}\n").bytes());
+        let records: Vec<_> = CatalogReader::new(catalog.as_slice()).collect();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[0].as_ref().unwrap().name.as_deref(),
+            Some("ISS \u{fffd}")
+        );
+        assert!(matches!(
+            records[1],
+            Err(Error::MalformedCatalogEntry { line: 4, .. })
+        ));
+        assert_eq!(
+            records[2].as_ref().unwrap().name.as_deref(),
+            Some("ISS (ZARYA)")
+        );
+    }
+
+    #[test]
+    fn test_parse_options() -> Result<()> {
+        let hand_typed = format!("{}0", &ISS_LINE_1[..68]);
+        let catalog = format!("{hand_typed}\n{ISS_LINE_2}\n{ISS_LINE_1}\n{ISS_LINE_2}\n");
+        let options = PropagationOptions {
+            gravity_model: crate::GravityModel::Wgs72,
+            ..Default::default()
+        };
+
+        let lenient = CatalogReader::new(catalog.as_bytes()).collect::<Result<Vec<_>>>()?;
+        assert_eq!(lenient.len(), 2);
+
+        let strict: Vec<_> =
+            CatalogReader::with_options(catalog.as_bytes(), ParseMode::Strict, options).collect();
+        assert_eq!(strict.len(), 2);
+        assert!(strict[0].is_err());
+        assert_eq!(strict[1].as_ref().unwrap().tle.options(), options);
+        Ok(())
+    }
+
+    #[test]
+    fn test_recovers_after_bad_record() {
+        let truncated = &ISS_LINE_2[..40];
+        let catalog = format!(
+            "ISS (ZARYA)\n{ISS_LINE_1}\n{truncated}\nORPHAN\nISS (ZARYA)\n{ISS_LINE_1}\n{ISS_LINE_2}\n"
+        );
+        let records: Vec<_> = CatalogReader::new(catalog.as_bytes()).collect();
+
+        assert_eq!(records.len(), 3);
+        match &records[0] {
+            Err(Error::MalformedCatalogEntry { line, error }) => {
+                assert_eq!(*line, 1);
+                assert_eq!(
+                    **error,
+                    Error::MalformedTwoLineElement(TleParseError::WrongLength {
+                        line: 2,
+                        expected: 69,
+                        actual: 40
+                    })
+                );
+            }
+            other => panic!("Expected a malformed entry, got {:?}", other),
+        }
+        match &records[1] {
+            Err(Error::MalformedCatalogEntry { line, error }) => {
+                assert_eq!(*line, 4);
+                assert_eq!(
+                    **error,
+                    Error::MalformedTwoLineElement(TleParseError::MissingLine { line: 1 })
+                );
+            }
+            other => panic!("Expected a malformed entry, got {:?}", other),
+        }
+        assert_eq!(
+            records[2].as_ref().unwrap().name.as_deref(),
+            Some("ISS (ZARYA)")
+        );
+    }
+}
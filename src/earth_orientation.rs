@@ -0,0 +1,352 @@
+//! Earth orientation parameters published by the IERS.
+//!
+//! The rotation of the Earth is irregular, so precise conversions between inertial and Earth-fixed
+//! frames need measured values of UT1-UTC and of the motion of the pole. The IERS publishes these
+//! daily in the `finals2000A.all`/`finals.data` files of Bulletin A, which include predictions a
+//! year ahead, and in the final EOP C04 series.
+
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use uom::si::{
+    angle::second as arcsecond,
+    f64::{Angle, Time},
+    time::{millisecond, second},
+};
+
+use crate::{Error, Result, Ut1Source};
+
+/// The Earth orientation parameters at one instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarthOrientation {
+    /// The x coordinate of the celestial intermediate pole relative to the ITRF pole.
+    pub polar_motion_x: Angle,
+    /// The y coordinate of the celestial intermediate pole relative to the ITRF pole.
+    pub polar_motion_y: Angle,
+    pub ut1_minus_utc: Time,
+    /// The excess of the length of day over 86400 SI seconds.
+    pub length_of_day: Time,
+}
+
+impl EarthOrientation {
+    /// The same orientation with the pole placed at the ITRF pole, for conversions which stop at
+    /// the pseudo Earth-fixed frame.
+    pub fn without_polar_motion(self) -> Self {
+        EarthOrientation {
+            polar_motion_x: Angle::new::<arcsecond>(0.0),
+            polar_motion_y: Angle::new::<arcsecond>(0.0),
+            ..self
+        }
+    }
+}
+
+/// A table of daily Earth orientation parameters, interpolated to any instant.
+///
+/// The default table is empty and gives zero for every parameter, which treats UT1 as equal to
+/// UTC and ignores polar motion. Outside the range of a loaded table the nearest entry is used.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EarthOrientationParameters {
+    entries: Vec<Entry>,
+}
+
+/// The parameters for one day, in the units of the IERS files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Entry {
+    mjd: f64,
+    x_arcseconds: f64,
+    y_arcseconds: f64,
+    ut1_minus_utc_seconds: f64,
+    length_of_day_milliseconds: f64,
+}
+
+impl EarthOrientationParameters {
+    /// Parse the contents of an IERS `finals2000A.all`, `finals2000A.data` or `finals.data` file.
+    ///
+    /// The Bulletin A values are used. Days beyond the end of the predictions, which have no
+    /// UT1-UTC value, are skipped.
+    pub fn parse_finals(text: &str) -> Result<EarthOrientationParameters> {
+        let mut entries = vec![];
+        for (number, line) in text.lines().enumerate() {
+            // Columns 59-68 hold UT1-UTC, which is the last value given for predicted days.
+            if line.get(58..68).is_none_or(|f| f.trim().is_empty()) {
+                continue;
+            }
+
+            let value = |columns: std::ops::Range<usize>, name: &str| -> Result<f64> {
+                let field = line.get(columns).unwrap_or("").trim();
+                if field.is_empty() {
+                    return Ok(0.0);
+                }
+                field.parse().map_err(|_| {
+                    Error::MalformedEarthOrientationData(format!(
+                        "Invalid {} on line {}",
+                        name,
+                        number + 1
+                    ))
+                })
+            };
+
+            entries.push(Entry {
+                mjd: value(7..15, "MJD")?,
+                x_arcseconds: value(18..27, "polar motion x")?,
+                y_arcseconds: value(37..46, "polar motion y")?,
+                ut1_minus_utc_seconds: value(58..68, "UT1-UTC")?,
+                length_of_day_milliseconds: value(79..86, "LOD")?,
+            });
+        }
+
+        EarthOrientationParameters::from_entries(entries)
+    }
+
+    /// Parse the contents of an IERS EOP C04 file, in either the 14 or the 20 series layout.
+    ///
+    /// Header lines are recognized as those which do not start with a year.
+    pub fn parse_c04(text: &str) -> Result<EarthOrientationParameters> {
+        let mut entries = vec![];
+        for (number, line) in text.lines().enumerate() {
+            let tokens: Vec<_> = line.split_whitespace().collect();
+            if tokens.first().is_none_or(|t| t.parse::<u16>().is_err()) {
+                continue;
+            }
+
+            let invalid = || {
+                Error::MalformedEarthOrientationData(format!(
+                    "Invalid entry on line {}",
+                    number + 1
+                ))
+            };
+            let values = tokens
+                .iter()
+                .map(|t| t.parse::<f64>())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|_| invalid())?;
+
+            // The 20 series adds an hour column before the MJD, and moves LOD after the pole
+            // rates. Both series give LOD in seconds.
+            let entry = match values.as_slice() {
+                [_, _, _, _, mjd, x, y, dut1, _, _, _, _, lod, ..] if *mjd > 1000.0 => Entry {
+                    mjd: *mjd,
+                    x_arcseconds: *x,
+                    y_arcseconds: *y,
+                    ut1_minus_utc_seconds: *dut1,
+                    length_of_day_milliseconds: lod * 1000.0,
+                },
+                [_, _, _, mjd, x, y, dut1, lod, ..] => Entry {
+                    mjd: *mjd,
+                    x_arcseconds: *x,
+                    y_arcseconds: *y,
+                    ut1_minus_utc_seconds: *dut1,
+                    length_of_day_milliseconds: lod * 1000.0,
+                },
+                _ => return Err(invalid()),
+            };
+            entries.push(entry);
+        }
+
+        EarthOrientationParameters::from_entries(entries)
+    }
+
+    /// Read an IERS `finals2000A.all`, `finals2000A.data` or `finals.data` file.
+    pub fn from_finals_file(path: impl AsRef<Path>) -> Result<EarthOrientationParameters> {
+        EarthOrientationParameters::parse_finals(&read_file(path)?)
+    }
+
+    /// Read an IERS EOP C04 file.
+    pub fn from_c04_file(path: impl AsRef<Path>) -> Result<EarthOrientationParameters> {
+        EarthOrientationParameters::parse_c04(&read_file(path)?)
+    }
+
+    fn from_entries(entries: Vec<Entry>) -> Result<EarthOrientationParameters> {
+        if entries.is_empty() {
+            return Err(Error::MalformedEarthOrientationData(
+                "No entries were found".to_owned(),
+            ));
+        }
+        if entries.windows(2).any(|pair| pair[0].mjd >= pair[1].mjd) {
+            return Err(Error::MalformedEarthOrientationData(
+                "Entries are not in date order".to_owned(),
+            ));
+        }
+        Ok(EarthOrientationParameters { entries })
+    }
+
+    /// Get the parameters at an instant, interpolating linearly between days.
+    pub fn at(&self, utc: DateTime<Utc>) -> EarthOrientation {
+        let entry = self.interpolate(modified_julian_day(utc));
+        EarthOrientation {
+            polar_motion_x: Angle::new::<arcsecond>(entry.x_arcseconds),
+            polar_motion_y: Angle::new::<arcsecond>(entry.y_arcseconds),
+            ut1_minus_utc: Time::new::<second>(entry.ut1_minus_utc_seconds),
+            length_of_day: Time::new::<millisecond>(entry.length_of_day_milliseconds),
+        }
+    }
+
+    fn interpolate(&self, mjd: f64) -> Entry {
+        let after = self.entries.partition_point(|e| e.mjd <= mjd);
+        let (before, after) = match (after.checked_sub(1), self.entries.get(after)) {
+            (Some(before), Some(after)) => (self.entries[before], *after),
+            (Some(before), None) => return self.entries[before],
+            (None, Some(after)) => return *after,
+            (None, None) => {
+                return Entry {
+                    mjd,
+                    x_arcseconds: 0.0,
+                    y_arcseconds: 0.0,
+                    ut1_minus_utc_seconds: 0.0,
+                    length_of_day_milliseconds: 0.0,
+                }
+            }
+        };
+
+        let t = (mjd - before.mjd) / (after.mjd - before.mjd);
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+        // UT1-UTC jumps by a second at a leap second, which takes effect at the start of the later
+        // day, so the later value is brought back onto the same side before interpolating.
+        let leap = (after.ut1_minus_utc_seconds - before.ut1_minus_utc_seconds).round();
+
+        Entry {
+            mjd,
+            x_arcseconds: lerp(before.x_arcseconds, after.x_arcseconds),
+            y_arcseconds: lerp(before.y_arcseconds, after.y_arcseconds),
+            ut1_minus_utc_seconds: lerp(
+                before.ut1_minus_utc_seconds,
+                after.ut1_minus_utc_seconds - leap,
+            ),
+            length_of_day_milliseconds: lerp(
+                before.length_of_day_milliseconds,
+                after.length_of_day_milliseconds,
+            ),
+        }
+    }
+}
+
+impl Ut1Source for EarthOrientationParameters {
+    fn ut1_minus_utc(&self, utc: DateTime<Utc>) -> f64 {
+        self.interpolate(modified_julian_day(utc))
+            .ut1_minus_utc_seconds
+    }
+}
+
+fn read_file(path: impl AsRef<Path>) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| Error::IoError(e.to_string()))
+}
+
+/// Get the Modified Julian Date, the number of days since 17 Nov 1858 00:00, of a UTC instant.
+fn modified_julian_day(utc: DateTime<Utc>) -> f64 {
+    let mjd_epoch = NaiveDate::from_ymd_opt(1858, 11, 17)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc();
+    let since = utc - mjd_epoch;
+    (since.num_seconds() as f64 + since.subsec_nanos() as f64 * 1e-9) / 86_400.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_parse_finals() -> Result<()> {
+        let eop = EarthOrientationParameters::from_finals_file("test_data/finals2000A.txt")?;
+        assert_eq!(eop.entries.len(), 7);
+
+        let t = Utc.with_ymd_and_hms(2016, 12, 30, 0, 0, 0).unwrap();
+        let values = eop.at(t);
+        assert!(approx_eq!(
+            f64,
+            values.polar_motion_x.get::<arcsecond>(),
+            0.071788
+        ));
+        assert!(approx_eq!(
+            f64,
+            values.polar_motion_y.get::<arcsecond>(),
+            0.281544
+        ));
+        assert!(approx_eq!(
+            f64,
+            values.ut1_minus_utc.get::<second>(),
+            -0.408339
+        ));
+        assert!(approx_eq!(
+            f64,
+            values.length_of_day.get::<millisecond>(),
+            0.7394
+        ));
+
+        // Predictions without a LOD value are read as zero.
+        let t = Utc.with_ymd_and_hms(2017, 1, 4, 0, 0, 0).unwrap();
+        assert_eq!(eop.at(t).length_of_day.get::<millisecond>(), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_c04() -> Result<()> {
+        let c04 = EarthOrientationParameters::from_c04_file("test_data/eopc04.txt")?;
+        let finals = EarthOrientationParameters::from_finals_file("test_data/finals2000A.txt")?;
+
+        let t = Utc.with_ymd_and_hms(2017, 1, 2, 6, 0, 0).unwrap();
+        let (a, b) = (c04.at(t), finals.at(t));
+        assert!(approx_eq!(
+            f64,
+            a.ut1_minus_utc.get::<second>(),
+            b.ut1_minus_utc.get::<second>()
+        ));
+        assert!(approx_eq!(
+            f64,
+            a.length_of_day.get::<millisecond>(),
+            b.length_of_day.get::<millisecond>(),
+            epsilon = 1e-9
+        ));
+
+        let c20 = "# YR MM DD HH MJD x y UT1-UTC dX dY xrt yrt LOD\n\
+            2017 1 1 0 57754.00 0.072978 0.282625 0.5900979 0.0 0.0 0.0 0.0 0.0008102";
+        let c20 = EarthOrientationParameters::parse_c04(c20)?;
+        assert_eq!(c20.entries, c04.entries[3..4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interpolation() -> Result<()> {
+        let eop = EarthOrientationParameters::from_finals_file("test_data/finals2000A.txt")?;
+
+        let t = Utc.with_ymd_and_hms(2016, 12, 29, 12, 0, 0).unwrap();
+        assert!(approx_eq!(
+            f64,
+            eop.ut1_minus_utc(t),
+            (-0.4076123 - 0.4083390) / 2.0
+        ));
+
+        // Interpolation across the leap second at the end of 2016 follows the day before it.
+        let t = Utc.with_ymd_and_hms(2016, 12, 31, 18, 0, 0).unwrap();
+        assert!(approx_eq!(
+            f64,
+            eop.ut1_minus_utc(t),
+            -0.4091043 + (0.5900979 - 1.0 + 0.4091043) * 0.75
+        ));
+
+        // The nearest entries are used outside the table.
+        let t = Utc.with_ymd_and_hms(2010, 1, 1, 0, 0, 0).unwrap();
+        assert!(approx_eq!(f64, eop.ut1_minus_utc(t), -0.4076123));
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_fallback() {
+        let t = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let eop = EarthOrientationParameters::default();
+        assert_eq!(eop.ut1_minus_utc(t), 0.0);
+        assert_eq!(eop.at(t).polar_motion_x.get::<arcsecond>(), 0.0);
+    }
+
+    #[test]
+    fn test_malformed_files() {
+        assert!(EarthOrientationParameters::parse_finals("").is_err());
+        assert!(EarthOrientationParameters::parse_c04("2017 1 1 57754 0.07 x").is_err());
+        assert!(EarthOrientationParameters::from_finals_file("test_data/missing").is_err());
+    }
+}
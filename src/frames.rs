@@ -0,0 +1,1150 @@
+//! Reference frames, and conversions between the TEME frame of SGP4 and the others.
+//!
+//! State vectors and their position and velocity vectors carry their frame as a type parameter, so
+//! that vectors in different frames cannot be mixed by mistake.
+//!
+//! SGP4 produces states in the True Equator, Mean Equinox (TEME) frame, an inertial frame of date.
+//! Rotating it by Greenwich mean sidereal time gives the pseudo Earth-fixed (PEF) frame, and
+//! correcting for polar motion gives the International Terrestrial Reference Frame (ITRF), the usual
+//! Earth-centred, Earth-fixed (ECEF) frame of GNSS receivers and maps.
+//!
+//! The inertial frames of date and of J2000 are related to TEME by the IAU 1976 precession and IAU
+//! 1980 nutation of the FK5 reduction, following Vallado et al., "Revisiting Spacetrack Report #3".
+//! With the celestial pole offsets observed by the IERS this realises the GCRF closely enough for
+//! SGP4, whose own errors are of the order of a kilometre. The IAU 2006 precession and IAU 2000B
+//! nutation can be used instead, with [PrecessionNutationModel::Iau2006_2000B], in which case the
+//! GCRF is reached from TEME through the celestial intermediate origin and the Earth rotation angle.
+
+use std::marker::PhantomData;
+use std::ops::{Add, Deref, DerefMut, Mul, Neg, Sub};
+
+use chrono::{DateTime, Duration, Utc};
+use uom::si::{
+    angle::radian,
+    f64::{Angle, Length, Velocity},
+    length::kilometer,
+    time::second,
+    velocity::kilometer_per_second,
+};
+
+use crate::iau2006::IntermediatePole;
+use crate::nutation::{self, Nutation, ARCSECONDS_TO_RADIANS};
+use crate::{
+    sgp4_sys, ClassicalOrbitalElements, EarthOrientation, EarthOrientationParameters,
+    EarthRotationAngle, Ellipsoid, Epoch, GeodeticPosition, GravityModel,
+    GreenwichMeanSiderealTime, LeapSecondTable, StateVector, TimeScale,
+};
+
+/// The nominal rotation rate of the Earth, in rad/s, for a day of exactly 86400 SI seconds.
+const EARTH_ROTATION_RATE: f64 = 7.292115146706979e-5;
+
+pub(crate) type Matrix = [[f64; 3]; 3];
+
+pub(crate) fn multiply(m: &Matrix, v: &[f64; 3]) -> [f64; 3] {
+    [0, 1, 2].map(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+}
+
+pub(crate) fn transpose(m: &Matrix) -> Matrix {
+    [0, 1, 2].map(|i| [m[0][i], m[1][i], m[2][i]])
+}
+
+pub(crate) fn product(a: &Matrix, b: &Matrix) -> Matrix {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j]))
+}
+
+const IDENTITY: Matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// The rotation of coordinates into a frame turned by `angle` about the x axis.
+pub(crate) fn rotation_x(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+/// The rotation of coordinates into a frame turned by `angle` about the y axis.
+pub(crate) fn rotation_y(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]]
+}
+
+/// The rotation of coordinates into a frame turned by `angle` about the z axis.
+pub(crate) fn rotation_z(angle: f64) -> Matrix {
+    let (s, c) = angle.sin_cos();
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+/// The polar motion matrix, taking ITRF coordinates to PEF.
+fn polar_motion(orientation: &EarthOrientation) -> Matrix {
+    let (sx, cx) = orientation.polar_motion_x.get::<radian>().sin_cos();
+    let (sy, cy) = orientation.polar_motion_y.get::<radian>().sin_cos();
+    [
+        [cx, 0.0, -sx],
+        [sx * sy, cy, cx * sy],
+        [sx * cy, -sy, cx * cy],
+    ]
+}
+
+/// The Greenwich mean sidereal time, and the Earth's angular velocity, at a UTC instant.
+fn earth_rotation(
+    epoch: DateTime<Utc>,
+    orientation: &EarthOrientation,
+) -> (GreenwichMeanSiderealTime, [f64; 3]) {
+    let ut1_minus_utc = orientation.ut1_minus_utc.get::<second>();
+    let ut1 = epoch + Duration::nanoseconds((ut1_minus_utc * 1e9).round() as i64);
+    let gmst = GreenwichMeanSiderealTime::from(ut1);
+    let rate = EARTH_ROTATION_RATE * (1.0 - orientation.length_of_day.get::<second>() / 86400.0);
+    (gmst, [0.0, 0.0, rate])
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A reference frame, used as a type parameter to keep vectors in different frames apart.
+///
+/// This trait is sealed; the frames are the marker types of this module.
+pub trait Frame: sealed::Sealed + Copy + std::fmt::Debug + PartialEq {
+    /// The orbital elements kept with a state in this frame: [ClassicalOrbitalElements] in inertial
+    /// frames, and `()` in the others, where osculating elements have no meaning.
+    type Elements: Copy + std::fmt::Debug;
+
+    /// The name of the frame, following the reference frame names of the CCSDS orbit data messages
+    /// where they have one.
+    const NAME: &'static str;
+
+    #[doc(hidden)]
+    fn elements(
+        position: &[f64; 3],
+        velocity: &[f64; 3],
+        gravity_model: GravityModel,
+    ) -> Self::Elements;
+}
+
+/// An inertial frame, related to TEME by precession and nutation.
+pub trait InertialFrame: Frame<Elements = ClassicalOrbitalElements> {
+    #[doc(hidden)]
+    fn rotation_from_teme(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> Matrix;
+}
+
+/// The True Equator, Mean Equinox frame of SGP4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Teme;
+
+/// The true equator and equinox of date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TrueOfDate;
+
+/// The mean equator and equinox of date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MeanOfDate;
+
+/// The mean equator and equinox of J2000.0, also known as EME2000, from the FK5 reduction without
+/// celestial pole offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct J2000;
+
+/// The Geocentric Celestial Reference Frame, as realised by the FK5 reduction with the celestial
+/// pole offsets observed by the IERS, or by the IAU 2006/2000B models. Without offsets the FK5
+/// realisation is the same as [J2000], and differs from the GCRF by about a metre at LEO altitudes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Gcrf;
+
+/// The International Terrestrial Reference Frame, the usual Earth-centred, Earth-fixed frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Itrf;
+
+/// A local east, north, up frame centred on an observer on the Earth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Topocentric;
+
+impl sealed::Sealed for Teme {}
+impl sealed::Sealed for TrueOfDate {}
+impl sealed::Sealed for MeanOfDate {}
+impl sealed::Sealed for J2000 {}
+impl sealed::Sealed for Gcrf {}
+impl sealed::Sealed for Itrf {}
+impl sealed::Sealed for Topocentric {}
+
+fn classical_elements(
+    position: &[f64; 3],
+    velocity: &[f64; 3],
+    gravity_model: GravityModel,
+) -> ClassicalOrbitalElements {
+    sgp4_sys::to_classical_elements(position, velocity, gravity_model.into()).into()
+}
+
+impl Frame for Teme {
+    type Elements = ClassicalOrbitalElements;
+    const NAME: &'static str = "TEME";
+
+    fn elements(p: &[f64; 3], v: &[f64; 3], gm: GravityModel) -> ClassicalOrbitalElements {
+        classical_elements(p, v, gm)
+    }
+}
+
+impl Frame for TrueOfDate {
+    type Elements = ClassicalOrbitalElements;
+    const NAME: &'static str = "TOD";
+
+    fn elements(p: &[f64; 3], v: &[f64; 3], gm: GravityModel) -> ClassicalOrbitalElements {
+        classical_elements(p, v, gm)
+    }
+}
+
+impl Frame for MeanOfDate {
+    type Elements = ClassicalOrbitalElements;
+    const NAME: &'static str = "MOD";
+
+    fn elements(p: &[f64; 3], v: &[f64; 3], gm: GravityModel) -> ClassicalOrbitalElements {
+        classical_elements(p, v, gm)
+    }
+}
+
+impl Frame for J2000 {
+    type Elements = ClassicalOrbitalElements;
+    const NAME: &'static str = "EME2000";
+
+    fn elements(p: &[f64; 3], v: &[f64; 3], gm: GravityModel) -> ClassicalOrbitalElements {
+        classical_elements(p, v, gm)
+    }
+}
+
+impl Frame for Gcrf {
+    type Elements = ClassicalOrbitalElements;
+    const NAME: &'static str = "GCRF";
+
+    fn elements(p: &[f64; 3], v: &[f64; 3], gm: GravityModel) -> ClassicalOrbitalElements {
+        classical_elements(p, v, gm)
+    }
+}
+
+impl Frame for Itrf {
+    type Elements = ();
+    const NAME: &'static str = "ITRF";
+
+    fn elements(_: &[f64; 3], _: &[f64; 3], _: GravityModel) {}
+}
+
+impl Frame for Topocentric {
+    type Elements = ();
+    const NAME: &'static str = "TOPOCENTRIC";
+
+    fn elements(_: &[f64; 3], _: &[f64; 3], _: GravityModel) {}
+}
+
+impl InertialFrame for Teme {
+    fn rotation_from_teme(
+        _: DateTime<Utc>,
+        _: &LeapSecondTable,
+        _: &CelestialPoleOffsets,
+        _: PrecessionNutationModel,
+    ) -> Matrix {
+        IDENTITY
+    }
+}
+
+impl InertialFrame for TrueOfDate {
+    fn rotation_from_teme(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> Matrix {
+        match model {
+            PrecessionNutationModel::Fk5 => Fk5::at(epoch, leap_seconds, offsets).true_of_date,
+            PrecessionNutationModel::Iau2006_2000B => {
+                let cio = Cio::at(epoch, leap_seconds, offsets);
+                cio.rotation_to(&cio.true_of_date)
+            }
+        }
+    }
+}
+
+impl InertialFrame for MeanOfDate {
+    fn rotation_from_teme(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> Matrix {
+        match model {
+            PrecessionNutationModel::Fk5 => {
+                let fk5 = Fk5::at(epoch, leap_seconds, offsets);
+                product(&fk5.mean_of_date, &fk5.true_of_date)
+            }
+            PrecessionNutationModel::Iau2006_2000B => {
+                let cio = Cio::at(epoch, leap_seconds, offsets);
+                cio.rotation_to(&cio.mean_of_date)
+            }
+        }
+    }
+}
+
+impl InertialFrame for J2000 {
+    fn rotation_from_teme(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        _: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> Matrix {
+        let offsets = CelestialPoleOffsets::default();
+        match model {
+            PrecessionNutationModel::Fk5 => Fk5::at(epoch, leap_seconds, &offsets).mean_of_j2000(),
+            PrecessionNutationModel::Iau2006_2000B => {
+                let cio = Cio::at(epoch, leap_seconds, &offsets);
+                cio.rotation_to(&cio.mean_of_j2000)
+            }
+        }
+    }
+}
+
+impl InertialFrame for Gcrf {
+    fn rotation_from_teme(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> Matrix {
+        match model {
+            PrecessionNutationModel::Fk5 => Fk5::at(epoch, leap_seconds, offsets).mean_of_j2000(),
+            PrecessionNutationModel::Iau2006_2000B => {
+                transpose(&Cio::at(epoch, leap_seconds, offsets).teme)
+            }
+        }
+    }
+}
+
+/// A vector of three Cartesian components in the frame `F`.
+///
+/// Vectors can be added and subtracted only within one frame, and dereference to the array of
+/// their components:
+///
+/// ```
+/// use sgp4_rs::{FrameVector, Teme};
+///
+/// let a = FrameVector::<Teme>::new([1.0, 2.0, 3.0]);
+/// let b = FrameVector::<Teme>::new([1.0, 1.0, 1.0]);
+/// assert_eq!((a - b)[2], 2.0);
+/// ```
+///
+/// ```compile_fail
+/// use sgp4_rs::{FrameVector, Itrf, Teme};
+///
+/// let a = FrameVector::<Teme>::new([1.0, 2.0, 3.0]);
+/// let b = FrameVector::<Itrf>::new([1.0, 1.0, 1.0]);
+/// let _ = a - b;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameVector<F> {
+    components: [f64; 3],
+    frame: PhantomData<F>,
+}
+
+impl<F: Frame> FrameVector<F> {
+    pub fn new(components: [f64; 3]) -> Self {
+        FrameVector {
+            components,
+            frame: PhantomData,
+        }
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn dot(&self, other: &Self) -> f64 {
+        (0..3)
+            .map(|i| self.components[i] * other.components[i])
+            .sum()
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        FrameVector::new(cross(&self.components, &other.components))
+    }
+
+    /// Create a position vector, held in km.
+    pub fn from_lengths(lengths: [Length; 3]) -> Self {
+        FrameVector::new(lengths.map(|l| l.get::<kilometer>()))
+    }
+
+    /// Create a velocity vector, held in km/s.
+    pub fn from_velocities(velocities: [Velocity; 3]) -> Self {
+        FrameVector::new(velocities.map(|v| v.get::<kilometer_per_second>()))
+    }
+
+    /// The components of a position vector, held in km.
+    pub fn lengths(&self) -> [Length; 3] {
+        self.components.map(Length::new::<kilometer>)
+    }
+
+    /// The components of a velocity vector, held in km/s.
+    pub fn velocities(&self) -> [Velocity; 3] {
+        self.components.map(Velocity::new::<kilometer_per_second>)
+    }
+
+    fn rotate<G: Frame>(&self, rotation: &Matrix) -> FrameVector<G> {
+        FrameVector::new(multiply(rotation, &self.components))
+    }
+}
+
+impl<F: Frame> From<[f64; 3]> for FrameVector<F> {
+    fn from(components: [f64; 3]) -> Self {
+        FrameVector::new(components)
+    }
+}
+
+impl<F: Frame> From<FrameVector<F>> for [f64; 3] {
+    fn from(v: FrameVector<F>) -> Self {
+        v.components
+    }
+}
+
+impl<F> Deref for FrameVector<F> {
+    type Target = [f64; 3];
+
+    fn deref(&self) -> &[f64; 3] {
+        &self.components
+    }
+}
+
+impl<F> DerefMut for FrameVector<F> {
+    fn deref_mut(&mut self) -> &mut [f64; 3] {
+        &mut self.components
+    }
+}
+
+impl<F: Frame> Add for FrameVector<F> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        FrameVector::new([0, 1, 2].map(|i| self.components[i] + other.components[i]))
+    }
+}
+
+impl<F: Frame> Sub for FrameVector<F> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        FrameVector::new([0, 1, 2].map(|i| self.components[i] - other.components[i]))
+    }
+}
+
+impl<F: Frame> Neg for FrameVector<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        FrameVector::new(self.components.map(|c| -c))
+    }
+}
+
+impl<F: Frame> Mul<f64> for FrameVector<F> {
+    type Output = Self;
+
+    fn mul(self, scale: f64) -> Self {
+        FrameVector::new(self.components.map(|c| c * scale))
+    }
+}
+
+impl StateVector<Itrf> {
+    /// Convert to the TEME frame, with the Earth orientation interpolated from `eop`.
+    ///
+    /// This is the inverse of [StateVector::to_ecef]. The result can be fitted with a TLE by
+    /// `as_tle_at` when the `tlegen` feature is enabled.
+    pub fn to_teme(&self, eop: &EarthOrientationParameters) -> StateVector {
+        self.to_teme_with_orientation(&eop.at(self.epoch))
+    }
+
+    /// Convert to the TEME frame with a given Earth orientation.
+    pub fn to_teme_with_orientation(&self, orientation: &EarthOrientation) -> StateVector {
+        let (gmst, omega) = earth_rotation(self.epoch, orientation);
+        let polar = polar_motion(orientation);
+        let sidereal = transpose(&rotation_z(gmst.as_radians()));
+
+        let position = multiply(&polar, &self.position);
+        let velocity = multiply(&polar, &self.velocity);
+        let rotation = cross(&omega, &position);
+        let velocity = [0, 1, 2].map(|i| velocity[i] + rotation[i]);
+        StateVector::with_gravity_model(
+            self.epoch,
+            multiply(&sidereal, &position),
+            multiply(&sidereal, &velocity),
+            self.gravity_model,
+        )
+    }
+
+    /// Convert to the east, north, up frame of an observer fixed to the Earth.
+    ///
+    /// The position is then relative to the observer, and the velocity relative to the ground.
+    pub fn to_topocentric(
+        &self,
+        observer: &GeodeticPosition,
+        ellipsoid: &Ellipsoid,
+    ) -> StateVector<Topocentric> {
+        let rotation = FrameRotation::topocentric(observer);
+        let relative = self.position - observer.to_ecef_position(ellipsoid).into();
+        StateVector::from_vectors_with_gravity_model(
+            self.epoch,
+            rotation.apply(&relative),
+            rotation.apply(&self.velocity),
+            self.gravity_model,
+        )
+    }
+}
+
+impl StateVector {
+    /// Convert to the ITRF, with the Earth orientation interpolated from `eop`.
+    ///
+    /// The velocity is relative to the rotating Earth, so it includes the ω×r term. Pass
+    /// `EarthOrientationParameters::default()` to treat UT1 as UTC and ignore polar motion.
+    pub fn to_ecef(&self, eop: &EarthOrientationParameters) -> StateVector<Itrf> {
+        self.to_ecef_with_orientation(&eop.at(self.epoch))
+    }
+
+    /// Convert to the ITRF with a given Earth orientation.
+    ///
+    /// Use [EarthOrientation::without_polar_motion] to stop at the pseudo Earth-fixed frame.
+    pub fn to_ecef_with_orientation(&self, orientation: &EarthOrientation) -> StateVector<Itrf> {
+        let (gmst, omega) = earth_rotation(self.epoch, orientation);
+        let polar = transpose(&polar_motion(orientation));
+        let sidereal = rotation_z(gmst.as_radians());
+
+        let position = multiply(&sidereal, &self.position);
+        let velocity = multiply(&sidereal, &self.velocity);
+        let rotation = cross(&omega, &position);
+        let velocity = [0, 1, 2].map(|i| velocity[i] - rotation[i]);
+        StateVector::from_vectors_with_gravity_model(
+            self.epoch,
+            FrameVector::new(multiply(&polar, &position)),
+            FrameVector::new(multiply(&polar, &velocity)),
+            self.gravity_model,
+        )
+    }
+}
+
+/// Observed corrections to the modelled position of the celestial pole, as published by the IERS in
+/// Bulletin A and the EOP C04 series: dψ and dε for the IAU 1980 nutation, and dX and dY for the
+/// IAU 2006/2000A models, which also apply to IAU 2006/2000B.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CelestialPoleOffsets {
+    /// The correction to the nutation in longitude, used by [PrecessionNutationModel::Fk5].
+    pub longitude: Angle,
+    /// The correction to the nutation in obliquity, used by [PrecessionNutationModel::Fk5].
+    pub obliquity: Angle,
+    /// The correction to the X coordinate of the celestial intermediate pole, used by
+    /// [PrecessionNutationModel::Iau2006_2000B].
+    pub x: Angle,
+    /// The correction to the Y coordinate of the celestial intermediate pole, used by
+    /// [PrecessionNutationModel::Iau2006_2000B].
+    pub y: Angle,
+}
+
+impl Default for CelestialPoleOffsets {
+    fn default() -> Self {
+        CelestialPoleOffsets {
+            longitude: Angle::new::<radian>(0.0),
+            obliquity: Angle::new::<radian>(0.0),
+            x: Angle::new::<radian>(0.0),
+            y: Angle::new::<radian>(0.0),
+        }
+    }
+}
+
+/// The models of precession and nutation which relate the inertial frames to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrecessionNutationModel {
+    /// The IAU 1976 precession and IAU 1980 nutation of the FK5 reduction, against which TEME is
+    /// defined.
+    #[default]
+    Fk5,
+    /// The IAU 2006 precession and the 77 term IAU 2000B nutation. This is not the full IAU
+    /// 2006/2000A model: IAU 2000B omits the smaller lunisolar terms and replaces the planetary
+    /// terms by a fixed offset, so it follows IAU 2000A to within a milliarcsecond rather than a
+    /// tenth of one. TEME is carried to the terrestrial intermediate frame by GMST, and from there
+    /// to the GCRF through the celestial intermediate origin by the Earth rotation angle.
+    Iau2006_2000B,
+}
+
+/// The IAU 1976 precession matrix, taking J2000 coordinates to the mean equator and equinox of the
+/// date `centuries` after J2000 in TT.
+fn precession(centuries: f64) -> Matrix {
+    let t = centuries;
+    let zeta = t * (2306.2181 + t * (0.30188 + t * 0.017998)) * ARCSECONDS_TO_RADIANS;
+    let theta = t * (2004.3109 + t * (-0.42665 - t * 0.041833)) * ARCSECONDS_TO_RADIANS;
+    let z = t * (2306.2181 + t * (1.09468 + t * 0.018203)) * ARCSECONDS_TO_RADIANS;
+    product(
+        &rotation_z(-z),
+        &product(&rotation_y(theta), &rotation_z(-zeta)),
+    )
+}
+
+/// The rotations of the FK5 reduction at a UTC instant.
+struct Fk5 {
+    /// From TEME to the true equator and equinox of date.
+    true_of_date: Matrix,
+    /// From the true to the mean equator and equinox of date.
+    mean_of_date: Matrix,
+    /// From the mean equator and equinox of date to J2000.
+    mean_of_j2000: Matrix,
+}
+
+impl Fk5 {
+    fn at(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+    ) -> Self {
+        let tt = Epoch::from(epoch).to_scale(TimeScale::Tt, leap_seconds, &0.0);
+        let t = nutation::julian_centuries(tt.into());
+
+        let mut nutation = Nutation::at(t);
+        nutation.longitude += offsets.longitude.get::<radian>();
+        nutation.obliquity += offsets.obliquity.get::<radian>();
+        Fk5 {
+            true_of_date: rotation_z(-nutation.equation_of_equinoxes()),
+            mean_of_date: product(
+                &rotation_x(-nutation.mean_obliquity),
+                &product(
+                    &rotation_z(nutation.longitude),
+                    &rotation_x(nutation.mean_obliquity + nutation.obliquity),
+                ),
+            ),
+            mean_of_j2000: transpose(&precession(t)),
+        }
+    }
+
+    /// The rotation from TEME to the mean equator and equinox of J2000.
+    fn mean_of_j2000(&self) -> Matrix {
+        product(
+            &self.mean_of_j2000,
+            &product(&self.mean_of_date, &self.true_of_date),
+        )
+    }
+}
+
+/// The rotations of the IAU 2006/2000B models at a UTC instant, each from the GCRF.
+struct Cio {
+    teme: Matrix,
+    true_of_date: Matrix,
+    mean_of_date: Matrix,
+    mean_of_j2000: Matrix,
+}
+
+impl Cio {
+    fn at(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+    ) -> Self {
+        let tt = Epoch::from(epoch).to_scale(TimeScale::Tt, leap_seconds, &0.0);
+        let pole = IntermediatePole::at(
+            nutation::julian_centuries(tt.into()),
+            offsets.x.get::<radian>(),
+            offsets.y.get::<radian>(),
+        );
+        let intermediate = pole.celestial_to_intermediate();
+
+        // TEME and the celestial intermediate frame are turned from the same terrestrial frame by
+        // GMST and by the Earth rotation angle. Only their difference is needed, which changes by
+        // microarcseconds when UT1 is taken as UTC.
+        let era = EarthRotationAngle::at(&epoch.into(), leap_seconds, &0.0).as_radians();
+        let gmst = sgp4_sys::datetime_to_gstime(epoch);
+        Cio {
+            teme: product(&rotation_z(era - gmst), &intermediate),
+            true_of_date: product(&rotation_z(pole.equation_of_origins), &intermediate),
+            mean_of_date: pole.mean_of_date,
+            mean_of_j2000: pole.mean_of_j2000,
+        }
+    }
+
+    /// The rotation from TEME to the frame reached from the GCRF by `from_gcrf`.
+    fn rotation_to(&self, from_gcrf: &Matrix) -> Matrix {
+        product(from_gcrf, &transpose(&self.teme))
+    }
+}
+
+impl<F: InertialFrame> StateVector<F> {
+    /// Convert to another inertial frame, with the FK5 reduction.
+    ///
+    /// The leap second table gives the TT used by the precession and nutation models, and the
+    /// offsets are applied in every frame of date, and in the GCRF.
+    pub fn to_frame<G: InertialFrame>(
+        &self,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+    ) -> StateVector<G> {
+        self.to_frame_with_model(leap_seconds, offsets, PrecessionNutationModel::Fk5)
+    }
+
+    /// Convert to another inertial frame, with the given precession and nutation models.
+    pub fn to_frame_with_model<G: InertialFrame>(
+        &self,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> StateVector<G> {
+        let rotation = FrameRotation::inertial_with_model(self.epoch, leap_seconds, offsets, model);
+        StateVector::from_vectors_with_gravity_model(
+            self.epoch,
+            rotation.apply(&self.position),
+            rotation.apply(&self.velocity),
+            self.gravity_model,
+        )
+    }
+}
+
+/// The rotation of coordinates from frame `F` to frame `G` at an instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRotation<F, G> {
+    matrix: Matrix,
+    frames: PhantomData<(F, G)>,
+}
+
+impl<F: Frame, G: Frame> FrameRotation<F, G> {
+    fn new(matrix: Matrix) -> Self {
+        FrameRotation {
+            matrix,
+            frames: PhantomData,
+        }
+    }
+
+    /// The rotation matrix, by rows, which multiplies column vectors in `F` to give them in `G`.
+    pub fn matrix(&self) -> [[f64; 3]; 3] {
+        self.matrix
+    }
+
+    pub fn apply(&self, v: &FrameVector<F>) -> FrameVector<G> {
+        v.rotate(&self.matrix)
+    }
+
+    pub fn inverse(&self) -> FrameRotation<G, F> {
+        FrameRotation::new(transpose(&self.matrix))
+    }
+}
+
+impl<F: InertialFrame, G: InertialFrame> FrameRotation<F, G> {
+    /// The rotation between inertial frames at a UTC instant, from the FK5 reduction.
+    ///
+    /// See [StateVector::to_frame] for the parameters.
+    pub fn inertial(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+    ) -> Self {
+        FrameRotation::inertial_with_model(
+            epoch,
+            leap_seconds,
+            offsets,
+            PrecessionNutationModel::Fk5,
+        )
+    }
+
+    /// The rotation between inertial frames at a UTC instant, from the given precession and
+    /// nutation models.
+    pub fn inertial_with_model(
+        epoch: DateTime<Utc>,
+        leap_seconds: &LeapSecondTable,
+        offsets: &CelestialPoleOffsets,
+        model: PrecessionNutationModel,
+    ) -> Self {
+        let from = F::rotation_from_teme(epoch, leap_seconds, offsets, model);
+        let to = G::rotation_from_teme(epoch, leap_seconds, offsets, model);
+        FrameRotation::new(product(&to, &transpose(&from)))
+    }
+}
+
+impl FrameRotation<Teme, Itrf> {
+    /// The rotation from TEME to the ITRF at a UTC instant.
+    ///
+    /// This rotates positions only: velocities relative to the Earth also need the ω×r term
+    /// applied by [StateVector::to_ecef].
+    pub fn earth_fixed(epoch: DateTime<Utc>, orientation: &EarthOrientation) -> Self {
+        let (gmst, _) = earth_rotation(epoch, orientation);
+        FrameRotation::new(product(
+            &transpose(&polar_motion(orientation)),
+            &rotation_z(gmst.as_radians()),
+        ))
+    }
+}
+
+impl FrameRotation<Itrf, Topocentric> {
+    /// The rotation from the ITRF to the east, north, up axes of an observer.
+    pub fn topocentric(observer: &GeodeticPosition) -> Self {
+        let (sin_lat, cos_lat) = observer.latitude.get::<radian>().sin_cos();
+        let (sin_lon, cos_lon) = observer.longitude.get::<radian>().sin_cos();
+        FrameRotation::new([
+            [-sin_lon, cos_lon, 0.0],
+            [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat],
+            [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{NaiveDate, TimeZone};
+    use float_cmp::approx_eq;
+    use uom::si::{
+        angle::{degree, second as arcsecond},
+        f64::{Angle, Time},
+    };
+
+    fn vecs_close(l: &[f64; 3], r: &[f64; 3], epsilon: f64) -> bool {
+        (0..3).all(|i| approx_eq!(f64, l[i], r[i], epsilon = epsilon))
+    }
+
+    /// The example of Vallado et al., "Revisiting Spacetrack Report #3", AIAA 2006-6753.
+    fn vallado_example() -> (StateVector, EarthOrientation) {
+        let epoch = NaiveDate::from_ymd_opt(2004, 4, 6)
+            .unwrap()
+            .and_hms_micro_opt(7, 51, 28, 386_009)
+            .unwrap()
+            .and_utc();
+        let teme = StateVector::new(
+            epoch,
+            [5094.18016210, 6127.64465950, 6380.34453270],
+            [-4.746131487, 0.785818041, 5.531931288],
+        );
+        let orientation = EarthOrientation {
+            polar_motion_x: Angle::new::<arcsecond>(-0.140682),
+            polar_motion_y: Angle::new::<arcsecond>(0.333309),
+            ut1_minus_utc: Time::new::<second>(-0.4399619),
+            length_of_day: Time::new::<second>(0.0015563),
+        };
+        (teme, orientation)
+    }
+
+    #[test]
+    fn test_teme_to_ecef() {
+        let (teme, orientation) = vallado_example();
+
+        let itrf = teme.to_ecef_with_orientation(&orientation);
+        assert!(vecs_close(
+            &itrf.position,
+            &[-1033.47938300, 7901.29527540, 6380.35659580],
+            1e-7
+        ));
+        assert!(vecs_close(
+            &itrf.velocity,
+            &[-3.225636520, -2.872451450, 5.531924446],
+            1e-9
+        ));
+
+        let rotation = FrameRotation::earth_fixed(teme.epoch, &orientation);
+        assert!(vecs_close(
+            &rotation.apply(&teme.position),
+            &itrf.position,
+            1e-9
+        ));
+        assert!(vecs_close(
+            &rotation.inverse().apply(&itrf.position),
+            &teme.position,
+            1e-9
+        ));
+
+        let pef = teme.to_ecef_with_orientation(&orientation.without_polar_motion());
+        assert!(vecs_close(
+            &pef.position,
+            &[-1033.47503130, 7901.30558560, 6380.34453270],
+            1e-7
+        ));
+        assert!(vecs_close(
+            &pef.velocity,
+            &[-3.225632747, -2.872442511, 5.531931288],
+            1e-9
+        ));
+    }
+
+    #[test]
+    fn test_ecef_to_teme() {
+        let (teme, orientation) = vallado_example();
+
+        let itrf = teme.to_ecef_with_orientation(&orientation);
+        let back = itrf.to_teme_with_orientation(&orientation);
+        assert!(vecs_close(&back.position, &teme.position, 1e-8));
+        assert!(vecs_close(&back.velocity, &teme.velocity, 1e-11));
+
+        // Without Earth orientation data the frames differ only by the rotation through GMST.
+        let epoch = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let eop = EarthOrientationParameters::default();
+        let gmst = crate::GreenwichMeanSiderealTime::from(epoch).as_radians();
+        let fixed = StateVector::<Itrf>::from_vectors(
+            epoch,
+            [7000.0, 0.0, 0.0].into(),
+            [0.0, 0.0, 7.5].into(),
+        );
+        let inertial = fixed.to_teme(&eop);
+        assert!(vecs_close(
+            &inertial.position,
+            &[7000.0 * gmst.cos(), 7000.0 * gmst.sin(), 0.0],
+            1e-9
+        ));
+        // A point fixed to the Earth moves eastwards in inertial space.
+        let speed = 7000.0 * EARTH_ROTATION_RATE;
+        assert!(vecs_close(
+            &inertial.velocity,
+            &[-speed * gmst.sin(), speed * gmst.cos(), 7.5],
+            1e-12
+        ));
+        assert!(vecs_close(
+            &inertial.to_ecef(&eop).velocity,
+            &fixed.velocity,
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn test_teme_to_inertial_frames() {
+        let (teme, _) = vallado_example();
+        let leap_seconds = LeapSecondTable::default();
+        let offsets = CelestialPoleOffsets {
+            longitude: Angle::new::<arcsecond>(-0.052195),
+            obliquity: Angle::new::<arcsecond>(-0.003875),
+            ..Default::default()
+        };
+        let tod: StateVector<TrueOfDate> = teme.to_frame(&leap_seconds, &offsets);
+        assert!(vecs_close(
+            &tod.position,
+            &[5094.51620300, 6127.36527840, 6380.34453270],
+            1e-7
+        ));
+
+        let mean_of_date = teme.to_frame::<MeanOfDate>(&leap_seconds, &offsets);
+        assert!(vecs_close(
+            &mean_of_date.position,
+            &[5094.02837450, 6127.87081640, 6380.24851640],
+            1e-7
+        ));
+        assert!(vecs_close(
+            &mean_of_date.velocity,
+            &[-4.746263052, 0.786014045, 5.531790562],
+            1e-9
+        ));
+
+        let gcrf = teme.to_frame::<Gcrf>(&leap_seconds, &offsets);
+        assert!(vecs_close(
+            &gcrf.position,
+            &[5102.50895790, 6123.01140070, 6378.13692820],
+            1e-7
+        ));
+        assert!(vecs_close(
+            &gcrf.velocity,
+            &[-4.743220157, 0.790536497, 5.533755727],
+            1e-9
+        ));
+
+        // J2000 ignores the offsets.
+        let j2000 = teme.to_frame::<J2000>(&leap_seconds, &offsets);
+        assert!(vecs_close(
+            &j2000.position,
+            &[5102.5096, 6123.01152, 6378.1363],
+            1e-4
+        ));
+        assert!(vecs_close(
+            &j2000.velocity,
+            &[-4.7432196, 0.7905366, 5.5337562],
+            1e-7
+        ));
+    }
+
+    #[test]
+    fn test_inertial_frame_round_trip() {
+        let (teme, _) = vallado_example();
+        let leap_seconds = LeapSecondTable::default();
+        let offsets = CelestialPoleOffsets {
+            longitude: Angle::new::<arcsecond>(-0.052195),
+            obliquity: Angle::new::<arcsecond>(-0.003875),
+            ..Default::default()
+        };
+
+        let gcrf = teme.to_frame::<Gcrf>(&leap_seconds, &offsets);
+        let back = gcrf.to_frame::<Teme>(&leap_seconds, &offsets);
+        assert!(vecs_close(&back.position, &teme.position, 1e-8));
+        assert!(vecs_close(&back.velocity, &teme.velocity, 1e-11));
+
+        let j2000 = gcrf.to_frame::<J2000>(&leap_seconds, &offsets);
+        let direct = teme.to_frame::<J2000>(&leap_seconds, &offsets);
+        assert!(vecs_close(&j2000.position, &direct.position, 1e-8));
+
+        // Elements are kept in every inertial frame.
+        let semimajor_axis = teme.semimajor_axis().get::<kilometer>();
+        assert!(approx_eq!(
+            f64,
+            j2000.semimajor_axis().get::<kilometer>(),
+            semimajor_axis,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[test]
+    fn test_gravity_model_is_kept() {
+        let (teme, orientation) = vallado_example();
+        let teme = StateVector::with_gravity_model(
+            teme.epoch,
+            *teme.position,
+            *teme.velocity,
+            GravityModel::Wgs72,
+        );
+        let leap_seconds = LeapSecondTable::default();
+        let offsets = CelestialPoleOffsets::default();
+
+        // The elements are unchanged by a rotation, when derived with the same gravity model.
+        let gcrf = teme.to_frame::<Gcrf>(&leap_seconds, &offsets);
+        assert_eq!(gcrf.gravity_model, GravityModel::Wgs72);
+        assert!(approx_eq!(
+            f64,
+            gcrf.semimajor_axis().get::<kilometer>(),
+            teme.semimajor_axis().get::<kilometer>(),
+            epsilon = 1e-6
+        ));
+        assert!(approx_eq!(
+            f64,
+            gcrf.eccentricity(),
+            teme.eccentricity(),
+            epsilon = 1e-12
+        ));
+        let back = gcrf.to_frame::<Teme>(&leap_seconds, &offsets);
+        assert!(approx_eq!(
+            f64,
+            back.mean_anomaly().get::<radian>(),
+            teme.mean_anomaly().get::<radian>(),
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            back.semimajor_axis().get::<kilometer>(),
+            teme.semimajor_axis().get::<kilometer>(),
+            epsilon = 1e-6
+        ));
+
+        let fixed = teme.to_ecef_with_orientation(&orientation);
+        assert_eq!(fixed.gravity_model, GravityModel::Wgs72);
+        let inertial = fixed.to_teme_with_orientation(&orientation);
+        assert_eq!(inertial.gravity_model, GravityModel::Wgs72);
+        assert!(approx_eq!(
+            f64,
+            inertial.semimajor_axis().get::<kilometer>(),
+            teme.semimajor_axis().get::<kilometer>(),
+            epsilon = 1e-6
+        ));
+    }
+
+    #[test]
+    fn test_iau2006_frames() {
+        let (teme, _) = vallado_example();
+        let leap_seconds = LeapSecondTable::default();
+        let model = PrecessionNutationModel::Iau2006_2000B;
+        // The IERS values of dX and dY for the date.
+        let offsets = CelestialPoleOffsets {
+            x: Angle::new::<arcsecond>(-0.000205),
+            y: Angle::new::<arcsecond>(-0.000136),
+            ..Default::default()
+        };
+
+        // The CIO-based transformation agrees with the FK5 reduction and its offsets to a centimetre.
+        let gcrf = teme.to_frame_with_model::<Gcrf>(&leap_seconds, &offsets, model);
+        assert!(vecs_close(
+            &gcrf.position,
+            &[5102.50895790, 6123.01140070, 6378.13692820],
+            1e-5
+        ));
+        assert!(vecs_close(
+            &gcrf.velocity,
+            &[-4.743220157, 0.790536497, 5.533755727],
+            1e-8
+        ));
+
+        let back = gcrf.to_frame_with_model::<Teme>(&leap_seconds, &offsets, model);
+        assert!(vecs_close(&back.position, &teme.position, 1e-8));
+        assert!(vecs_close(&back.velocity, &teme.velocity, 1e-11));
+
+        // The frames of date differ from those of FK5 by the metres that separate the precession
+        // models.
+        let fk5_offsets = CelestialPoleOffsets {
+            longitude: Angle::new::<arcsecond>(-0.052195),
+            obliquity: Angle::new::<arcsecond>(-0.003875),
+            ..Default::default()
+        };
+        let tod = teme.to_frame_with_model::<TrueOfDate>(&leap_seconds, &offsets, model);
+        let fk5_tod = teme.to_frame::<TrueOfDate>(&leap_seconds, &fk5_offsets);
+        assert!(vecs_close(&tod.position, &fk5_tod.position, 5e-3));
+        assert!(approx_eq!(
+            f64,
+            tod.position[2],
+            teme.position[2],
+            epsilon = 1e-9
+        ));
+        let mean_of_date = gcrf.to_frame_with_model::<MeanOfDate>(&leap_seconds, &offsets, model);
+        let fk5_mean_of_date = teme.to_frame::<MeanOfDate>(&leap_seconds, &fk5_offsets);
+        assert!(vecs_close(
+            &mean_of_date.position,
+            &fk5_mean_of_date.position,
+            5e-3
+        ));
+
+        let rotation = FrameRotation::<Gcrf, J2000>::inertial_with_model(
+            teme.epoch,
+            &leap_seconds,
+            &CelestialPoleOffsets::default(),
+            model,
+        );
+        let j2000 = teme.to_frame_with_model::<J2000>(&leap_seconds, &offsets, model);
+        let unbiased = teme.to_frame_with_model::<Gcrf>(&leap_seconds, &Default::default(), model);
+        assert!(vecs_close(
+            &j2000.position,
+            &rotation.apply(&unbiased.position),
+            1e-9
+        ));
+    }
+
+    #[test]
+    fn test_frame_vectors() {
+        let a = FrameVector::<Teme>::new([1.0, 2.0, 3.0]);
+        let b = FrameVector::<Teme>::new([4.0, 5.0, 6.0]);
+        assert_eq!(*(a + b), [5.0, 7.0, 9.0]);
+        assert_eq!(*(b - a), [3.0, 3.0, 3.0]);
+        assert_eq!(*(-a * 2.0), [-2.0, -4.0, -6.0]);
+        assert_eq!(a.dot(&b), 32.0);
+        assert_eq!(*a.cross(&b), [-3.0, 6.0, -3.0]);
+        assert!(approx_eq!(f64, a.norm(), 14.0_f64.sqrt()));
+        assert_eq!(a[1], 2.0);
+        assert_eq!(<[f64; 3]>::from(a), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_topocentric() {
+        let epoch = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let wgs84 = Ellipsoid::wgs84();
+        let observer = GeodeticPosition::new(
+            Angle::new::<degree>(45.0),
+            Angle::new::<degree>(10.0),
+            Length::new::<kilometer>(0.0),
+        );
+
+        // A point 500 km straight up, rising at 1 km/s.
+        let overhead = GeodeticPosition::new(
+            observer.latitude,
+            observer.longitude,
+            Length::new::<kilometer>(500.0),
+        );
+        let up = overhead.to_ecef_position(&wgs84);
+        let normal = [0, 1, 2].map(|i| (up[i] - observer.to_ecef_position(&wgs84)[i]) / 500.0);
+        let satellite = StateVector::<Itrf>::from_vectors(epoch, up.into(), normal.into());
+        let local = satellite.to_topocentric(&observer, &wgs84);
+        assert!(vecs_close(&local.position, &[0.0, 0.0, 500.0], 1e-9));
+        assert!(vecs_close(&local.velocity, &[0.0, 0.0, 1.0], 1e-12));
+    }
+}
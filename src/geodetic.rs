@@ -0,0 +1,298 @@
+//! Geodetic coordinates on a reference ellipsoid.
+
+use chrono::{DateTime, Utc};
+use uom::si::{
+    angle::radian,
+    f64::{Angle, Length},
+    length::kilometer,
+};
+
+use crate::{EarthOrientationParameters, Itrf, StateVector};
+
+/// A reference ellipsoid approximating the figure of the Earth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    pub equatorial_radius: Length,
+    pub flattening: f64,
+}
+
+impl Ellipsoid {
+    pub fn new(equatorial_radius: Length, flattening: f64) -> Self {
+        Ellipsoid {
+            equatorial_radius,
+            flattening,
+        }
+    }
+
+    /// The ellipsoid of the World Geodetic System 1984, used by GPS.
+    pub fn wgs84() -> Self {
+        Ellipsoid::new(Length::new::<kilometer>(6378.137), 1.0 / 298.257223563)
+    }
+
+    /// The ellipsoid of the World Geodetic System 1972.
+    pub fn wgs72() -> Self {
+        Ellipsoid::new(Length::new::<kilometer>(6378.135), 1.0 / 298.26)
+    }
+
+    /// The square of the first eccentricity.
+    fn eccentricity_squared(&self) -> f64 {
+        self.flattening * (2.0 - self.flattening)
+    }
+}
+
+impl Default for Ellipsoid {
+    fn default() -> Self {
+        Ellipsoid::wgs84()
+    }
+}
+
+/// A position given by geodetic latitude, longitude and height above a reference ellipsoid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeodeticPosition {
+    pub latitude: Angle,
+
+    /// The longitude, positive to the east, from -π to π.
+    pub longitude: Angle,
+
+    /// The height above the ellipsoid, along its normal.
+    pub height: Length,
+}
+
+impl GeodeticPosition {
+    pub fn new(latitude: Angle, longitude: Angle, height: Length) -> Self {
+        GeodeticPosition {
+            latitude,
+            longitude,
+            height,
+        }
+    }
+
+    /// Find the geodetic coordinates of an Earth-fixed position in km.
+    pub fn from_ecef(position: &[f64; 3], ellipsoid: &Ellipsoid) -> Self {
+        let [x, y, z] = *position;
+        let a = ellipsoid.equatorial_radius.get::<kilometer>();
+        let e2 = ellipsoid.eccentricity_squared();
+        let p = x.hypot(y);
+
+        // Iterate from the geocentric latitude; each step is accurate to far better than a
+        // millimetre after three or four iterations, even at high altitude.
+        let mut latitude = z.atan2(p * (1.0 - e2));
+        for _ in 0..10 {
+            let sin = latitude.sin();
+            let normal = a / (1.0 - e2 * sin * sin).sqrt();
+            let next = (z + e2 * normal * sin).atan2(p);
+            let converged = (next - latitude).abs() < 1e-15;
+            latitude = next;
+            if converged {
+                break;
+            }
+        }
+
+        // This form of the height is well conditioned at the poles as well as the equator.
+        let (sin, cos) = latitude.sin_cos();
+        let height = p * cos + z * sin - a * (1.0 - e2 * sin * sin).sqrt();
+        GeodeticPosition::new(
+            Angle::new::<radian>(latitude),
+            Angle::new::<radian>(y.atan2(x)),
+            Length::new::<kilometer>(height),
+        )
+    }
+
+    /// The Earth-fixed position in km.
+    pub fn to_ecef_position(&self, ellipsoid: &Ellipsoid) -> [f64; 3] {
+        let a = ellipsoid.equatorial_radius.get::<kilometer>();
+        let e2 = ellipsoid.eccentricity_squared();
+        let h = self.height.get::<kilometer>();
+        let (sin_lat, cos_lat) = self.latitude.get::<radian>().sin_cos();
+        let (sin_lon, cos_lon) = self.longitude.get::<radian>().sin_cos();
+        let normal = a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+        [
+            (normal + h) * cos_lat * cos_lon,
+            (normal + h) * cos_lat * sin_lon,
+            (normal * (1.0 - e2) + h) * sin_lat,
+        ]
+    }
+
+    /// The state of a point fixed to the Earth at this position.
+    pub fn to_ecef(&self, epoch: DateTime<Utc>, ellipsoid: &Ellipsoid) -> StateVector<Itrf> {
+        StateVector::from_vectors(
+            epoch,
+            self.to_ecef_position(ellipsoid).into(),
+            [0.0; 3].into(),
+        )
+    }
+
+    /// The inertial state of a point fixed to the Earth at this position, moving with the Earth's
+    /// rotation.
+    pub fn to_teme(
+        &self,
+        epoch: DateTime<Utc>,
+        eop: &EarthOrientationParameters,
+        ellipsoid: &Ellipsoid,
+    ) -> StateVector {
+        self.to_ecef(epoch, ellipsoid).to_teme(eop)
+    }
+}
+
+impl StateVector<Itrf> {
+    /// The geodetic coordinates of the position.
+    pub fn geodetic(&self, ellipsoid: &Ellipsoid) -> GeodeticPosition {
+        GeodeticPosition::from_ecef(&self.position, ellipsoid)
+    }
+}
+
+impl StateVector {
+    /// The geodetic coordinates of the position on the WGS84 ellipsoid, such as the sub-satellite
+    /// point and altitude.
+    ///
+    /// UT1 is taken as UTC and polar motion is ignored. As UT1-UTC can reach 0.9 s, during which the
+    /// equator turns by over 400 m, this can misplace a satellite in low Earth orbit by up to half a
+    /// kilometre; use [StateVector::geodetic_with] to supply Earth orientation parameters.
+    pub fn geodetic(&self) -> GeodeticPosition {
+        self.geodetic_with(&EarthOrientationParameters::default(), &Ellipsoid::wgs84())
+    }
+
+    /// The geodetic coordinates of the position, with the given Earth orientation parameters and
+    /// reference ellipsoid.
+    pub fn geodetic_with(
+        &self,
+        eop: &EarthOrientationParameters,
+        ellipsoid: &Ellipsoid,
+    ) -> GeodeticPosition {
+        self.to_ecef(eop).geodetic(ellipsoid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+    use uom::si::{angle::degree, length::meter};
+
+    use crate::{GreenwichMeanSiderealTime, Result, TwoLineElement};
+
+    #[test]
+    fn test_from_ecef() {
+        // Vallado, Fundamentals of Astrodynamics and Applications, example 3-3.
+        let position =
+            GeodeticPosition::from_ecef(&[6524.834, 6862.875, 6448.296], &Ellipsoid::wgs84());
+        assert!(approx_eq!(
+            f64,
+            position.latitude.get::<degree>(),
+            34.352496,
+            epsilon = 1e-5
+        ));
+        assert!(approx_eq!(
+            f64,
+            position.longitude.get::<degree>(),
+            46.4464,
+            epsilon = 1e-4
+        ));
+        assert!(approx_eq!(
+            f64,
+            position.height.get::<kilometer>(),
+            5085.22,
+            epsilon = 1e-2
+        ));
+
+        // The poles have no unique longitude, but are still handled.
+        let pole = GeodeticPosition::from_ecef(&[0.0, 0.0, -6400.0], &Ellipsoid::wgs84());
+        assert!(approx_eq!(f64, pole.latitude.get::<degree>(), -90.0));
+        assert!(approx_eq!(
+            f64,
+            pole.height.get::<kilometer>(),
+            6400.0 - 6356.752314245,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[test]
+    fn test_to_ecef() {
+        let wgs84 = Ellipsoid::wgs84();
+        let equator = GeodeticPosition::new(
+            Angle::new::<degree>(0.0),
+            Angle::new::<degree>(90.0),
+            Length::new::<kilometer>(1.0),
+        );
+        let position = equator.to_ecef_position(&wgs84);
+        assert!(approx_eq!(f64, position[0], 0.0, epsilon = 1e-12));
+        assert!(approx_eq!(f64, position[1], 6379.137, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position[2], 0.0, epsilon = 1e-12));
+
+        let pole = GeodeticPosition::new(
+            Angle::new::<degree>(90.0),
+            Angle::new::<degree>(0.0),
+            Length::new::<kilometer>(0.0),
+        );
+        let position = pole.to_ecef_position(&wgs84);
+        assert!(approx_eq!(f64, position[2], 6356.752314245, epsilon = 1e-9));
+
+        // The station of Vallado, Fundamentals of Astrodynamics and Applications, example 7-1.
+        let station = GeodeticPosition::new(
+            Angle::new::<degree>(39.007),
+            Angle::new::<degree>(-104.883),
+            Length::new::<meter>(2194.56),
+        );
+        let position = station.to_ecef_position(&wgs84);
+        let round_trip = GeodeticPosition::from_ecef(&position, &Ellipsoid::wgs84());
+        assert!(approx_eq!(
+            f64,
+            round_trip.latitude.get::<radian>(),
+            station.latitude.get::<radian>(),
+            epsilon = 1e-14
+        ));
+        assert!(approx_eq!(
+            f64,
+            round_trip.longitude.get::<radian>(),
+            station.longitude.get::<radian>(),
+            epsilon = 1e-14
+        ));
+        assert!(approx_eq!(
+            f64,
+            round_trip.height.get::<meter>(),
+            2194.56,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[test]
+    fn test_sub_satellite_point() -> Result<()> {
+        let tle = TwoLineElement::new(
+            "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992",
+            "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767",
+        )?;
+        let epoch = Utc.with_ymd_and_hms(2020, 5, 27, 12, 0, 0).unwrap();
+        let state = tle.propagate_to(epoch)?;
+        let point = state.geodetic();
+
+        // The ISS orbits at about 420 km, and never beyond its inclination.
+        let altitude = point.height.get::<kilometer>();
+        assert!((400.0..440.0).contains(&altitude));
+        assert!(point.latitude.get::<degree>().abs() < 51.7);
+
+        // The longitude is the right ascension less the sidereal angle.
+        let gmst = GreenwichMeanSiderealTime::from(epoch).as_radians();
+        let right_ascension = state.position[1].atan2(state.position[0]);
+        let longitude = point.longitude.get::<radian>();
+        let difference = (right_ascension - gmst - longitude).rem_euclid(std::f64::consts::TAU);
+        assert!(difference < 1e-12 || std::f64::consts::TAU - difference < 1e-12);
+
+        // A ground target follows the Earth round in TEME.
+        let target = point.to_teme(
+            epoch,
+            &EarthOrientationParameters::default(),
+            &Ellipsoid::wgs84(),
+        );
+        let speed = (target.velocity[0].powi(2) + target.velocity[1].powi(2)).sqrt();
+        let radius = (target.position[0].powi(2) + target.position[1].powi(2)).sqrt();
+        assert!(approx_eq!(
+            f64,
+            speed / radius,
+            7.292115e-5,
+            epsilon = 1e-10
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,399 @@
+//! The IAU 2006 precession and IAU 2000B nutation, and the celestial intermediate pole and origin
+//! which they place.
+//!
+//! IAU 2000B is the 77 term abridgement of the IAU 2000A nutation series, which agrees with it to
+//! within a milliarcsecond between 1995 and 2050, with the adjustments made to the IAU 2000A series
+//! for consistency with the IAU 2006 precession. These follow the IAU SOFA routines `iauNut00b`,
+//! `iauNut06a`, `iauPfw06`, `iauFw2m`, `iauS06` and `iauEors`.
+
+use crate::frames::{product, rotation_x, rotation_y, rotation_z, Matrix};
+use crate::nutation::ARCSECONDS_TO_RADIANS;
+
+const ARCSECONDS_PER_TURN: f64 = 1_296_000.0;
+
+/// The Delaunay arguments of Simon et al. (1994), as used by IAU 2000B: the mean anomalies of the
+/// Moon and the Sun, the Moon's argument of latitude, the mean elongation of the Moon from the Sun,
+/// and the longitude of the Moon's ascending node, in radians.
+fn fundamental_arguments(centuries: f64) -> [f64; 5] {
+    let t = centuries;
+    let argument = |constant: f64, rate: f64| {
+        ((constant + rate * t) % ARCSECONDS_PER_TURN) * ARCSECONDS_TO_RADIANS
+    };
+    [
+        argument(485_868.249_036, 1_717_915_923.217_8),
+        argument(1_287_104.793_05, 129_596_581.048_1),
+        argument(335_779.526_232, 1_739_527_262.847_8),
+        argument(1_072_260.703_69, 1_602_961_601.209_0),
+        argument(450_160.398_036, -6_962_890.543_1),
+    ]
+}
+
+/// Nutation in longitude and obliquity, Δψ and Δε, in radians, from the IAU 2000B series.
+///
+/// The planetary terms of IAU 2000A are replaced by fixed offsets, as the series prescribes.
+fn nutation_2000b(centuries: f64) -> (f64, f64) {
+    let t = centuries;
+    let arguments = fundamental_arguments(t);
+    let (mut longitude, mut obliquity) = (0.0, 0.0);
+    // Sum the smallest terms first.
+    for (multipliers, [a, b, c, d, e, f]) in TERMS.iter().rev() {
+        let angle: f64 = multipliers
+            .iter()
+            .zip(arguments.iter())
+            .map(|(&m, &argument)| m as f64 * argument)
+            .sum();
+        let (sin, cos) = angle.sin_cos();
+        longitude += (a + b * t) * sin + c * cos;
+        obliquity += (d + e * t) * cos + f * sin;
+    }
+    (
+        (longitude * 1e-7 - 0.000_135) * ARCSECONDS_TO_RADIANS,
+        (obliquity * 1e-7 + 0.000_388) * ARCSECONDS_TO_RADIANS,
+    )
+}
+
+/// The Fukushima-Williams angles of the IAU 2006 precession, in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FukushimaWilliams {
+    gamma: f64,
+    phi: f64,
+    psi: f64,
+    /// The mean obliquity of the ecliptic.
+    epsilon: f64,
+}
+
+impl FukushimaWilliams {
+    fn at(centuries: f64) -> Self {
+        let t = centuries;
+        let polynomial = |c: [f64; 6]| {
+            (c[0] + t * (c[1] + t * (c[2] + t * (c[3] + t * (c[4] + t * c[5])))))
+                * ARCSECONDS_TO_RADIANS
+        };
+        FukushimaWilliams {
+            gamma: polynomial([
+                -0.052_928,
+                10.556_378,
+                0.493_204_4,
+                -0.000_312_38,
+                -0.000_002_788,
+                0.000_000_026_0,
+            ]),
+            phi: polynomial([
+                84_381.412_819,
+                -46.811_016,
+                0.051_126_8,
+                0.000_532_89,
+                -0.000_000_440,
+                -0.000_000_017_6,
+            ]),
+            psi: polynomial([
+                -0.041_775,
+                5_038.481_484,
+                1.558_417_5,
+                -0.000_185_22,
+                -0.000_026_452,
+                -0.000_000_014_8,
+            ]),
+            epsilon: polynomial([
+                84_381.406,
+                -46.836_769,
+                -0.000_183_1,
+                0.002_003_40,
+                -0.000_000_576,
+                -0.000_000_043_4,
+            ]),
+        }
+    }
+
+    /// The rotation from the GCRS to the mean equator and equinox of date, offset in longitude and
+    /// obliquity by the nutation to give the true equator and equinox of date.
+    fn matrix(&self, longitude: f64, obliquity: f64) -> Matrix {
+        product(
+            &product(
+                &rotation_x(-(self.epsilon + obliquity)),
+                &rotation_z(-(self.psi + longitude)),
+            ),
+            &product(&rotation_x(self.phi), &rotation_z(self.gamma)),
+        )
+    }
+}
+
+/// The CIO locator s, in radians, from the coordinates of the celestial intermediate pole.
+///
+/// Only the terms of the IERS 2003 series for s + XY/2 above half a microarcsecond are kept, and
+/// the rest amount to a few microarcseconds.
+fn cio_locator(centuries: f64, x: f64, y: f64) -> f64 {
+    let t = centuries;
+    let arguments = fundamental_arguments(t);
+    let mut coefficients = [
+        94.00e-6,
+        3_808.65e-6,
+        -122.68e-6,
+        -72_574.11e-6,
+        27.98e-6,
+        15.62e-6,
+    ];
+    for (power, multipliers, sin, cos) in CIO_LOCATOR_TERMS.iter().rev() {
+        let angle: f64 = multipliers
+            .iter()
+            .zip(arguments.iter())
+            .map(|(&m, &argument)| m as f64 * argument)
+            .sum();
+        coefficients[*power] += sin * angle.sin() + cos * angle.cos();
+    }
+    let series = coefficients
+        .iter()
+        .rev()
+        .fold(0.0, |sum, coefficient| sum * t + coefficient);
+    series * ARCSECONDS_TO_RADIANS - x * y / 2.0
+}
+
+/// The orientation of the celestial intermediate system at an instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct IntermediatePole {
+    /// The rotation from the GCRS to the true equator and equinox of date.
+    pub true_of_date: Matrix,
+    /// The rotation from the GCRS to the mean equator and equinox of date.
+    pub mean_of_date: Matrix,
+    /// The rotation from the GCRS to the mean equator and equinox of J2000, the frame bias.
+    pub mean_of_j2000: Matrix,
+    /// The coordinates of the celestial intermediate pole in the GCRS, in radians.
+    pub x: f64,
+    pub y: f64,
+    /// The CIO locator, in radians.
+    pub s: f64,
+    /// The equation of the origins, the angle from the equinox to the CIO along the true equator,
+    /// in radians.
+    pub equation_of_origins: f64,
+}
+
+impl IntermediatePole {
+    /// Evaluate the models at Julian centuries of TT since J2000.0, adding observed corrections to
+    /// the coordinates of the pole.
+    pub(crate) fn at(centuries: f64, dx: f64, dy: f64) -> Self {
+        let t = centuries;
+        let (longitude, obliquity) = nutation_2000b(t);
+        // The adjustments of the IAU 2000 nutation for the IAU 2006 precession, from iauNut06a.
+        let j2 = -2.7774e-6 * t;
+        let longitude = longitude * (1.0 + 0.4697e-6 + j2);
+        let obliquity = obliquity * (1.0 + j2);
+
+        let angles = FukushimaWilliams::at(t);
+        let true_of_date = angles.matrix(longitude, obliquity);
+        let mean_of_date = angles.matrix(0.0, 0.0);
+        let mean_of_j2000 = FukushimaWilliams::at(0.0).matrix(0.0, 0.0);
+
+        let (model_x, model_y) = (true_of_date[2][0], true_of_date[2][1]);
+        let equation_of_origins =
+            equation_of_origins(&true_of_date, cio_locator(t, model_x, model_y));
+        let (x, y) = (model_x + dx, model_y + dy);
+        IntermediatePole {
+            true_of_date,
+            mean_of_date,
+            mean_of_j2000,
+            x,
+            y,
+            s: cio_locator(t, x, y),
+            equation_of_origins,
+        }
+    }
+
+    /// The rotation from the GCRS to the celestial intermediate reference system.
+    pub(crate) fn celestial_to_intermediate(&self) -> Matrix {
+        let r2 = self.x * self.x + self.y * self.y;
+        let e = if r2 > 0.0 { self.y.atan2(self.x) } else { 0.0 };
+        let d = (r2 / (1.0 - r2)).sqrt().atan();
+        product(
+            &rotation_z(-(e + self.s)),
+            &product(&rotation_y(d), &rotation_z(e)),
+        )
+    }
+}
+
+/// The equation of the origins, from the rotation to the true equator and equinox of date and the
+/// CIO locator.
+fn equation_of_origins(true_of_date: &Matrix, s: f64) -> f64 {
+    let m = true_of_date;
+    let x = m[2][0];
+    let ax = x / (1.0 + m[2][2]);
+    let (xs, ys, zs) = (1.0 - ax * x, -ax * m[2][1], -x);
+    let p = m[0][0] * xs + m[0][1] * ys + m[0][2] * zs;
+    let q = m[1][0] * xs + m[1][1] * ys + m[1][2] * zs;
+    if p != 0.0 || q != 0.0 {
+        s - q.atan2(p)
+    } else {
+        s
+    }
+}
+
+/// The 77 terms of the IAU 2000B series: multipliers of the Delaunay arguments, then the
+/// coefficients of Δψ (sine, sine per century and cosine) and of Δε (cosine, cosine per century
+/// and sine), in units of 0.1 μas.
+#[rustfmt::skip]
+const TERMS: [([i8; 5], [f64; 6]); 77] = [
+    ([ 0, 0, 0, 0, 1], [-172_064_161.0, -174_666.0,  33_386.0, 92_052_331.0,  9_086.0, 15_377.0]),
+    ([ 0, 0, 2,-2, 2], [ -13_170_906.0,   -1_675.0, -13_696.0,  5_730_336.0, -3_015.0, -4_587.0]),
+    ([ 0, 0, 2, 0, 2], [  -2_276_413.0,     -234.0,   2_796.0,    978_459.0,   -485.0,  1_374.0]),
+    ([ 0, 0, 0, 0, 2], [   2_074_554.0,      207.0,    -698.0,   -897_492.0,    470.0,   -291.0]),
+    ([ 0, 1, 0, 0, 0], [   1_475_877.0,   -3_633.0,  11_817.0,     73_871.0,   -184.0, -1_924.0]),
+    ([ 0, 1, 2,-2, 2], [    -516_821.0,    1_226.0,    -524.0,    224_386.0,   -677.0,   -174.0]),
+    ([ 1, 0, 0, 0, 0], [     711_159.0,       73.0,    -872.0,     -6_750.0,      0.0,    358.0]),
+    ([ 0, 0, 2, 0, 1], [    -387_298.0,     -367.0,     380.0,    200_728.0,     18.0,    318.0]),
+    ([ 1, 0, 2, 0, 2], [    -301_461.0,      -36.0,     816.0,    129_025.0,    -63.0,    367.0]),
+    ([ 0,-1, 2,-2, 2], [     215_829.0,     -494.0,     111.0,    -95_929.0,    299.0,    132.0]),
+    ([ 0, 0, 2,-2, 1], [     128_227.0,      137.0,     181.0,    -68_982.0,     -9.0,     39.0]),
+    ([-1, 0, 2, 0, 2], [     123_457.0,       11.0,      19.0,    -53_311.0,     32.0,     -4.0]),
+    ([-1, 0, 0, 2, 0], [     156_994.0,       10.0,    -168.0,     -1_235.0,      0.0,     82.0]),
+    ([ 1, 0, 0, 0, 1], [      63_110.0,       63.0,      27.0,    -33_228.0,      0.0,     -9.0]),
+    ([-1, 0, 0, 0, 1], [     -57_976.0,      -63.0,    -189.0,     31_429.0,      0.0,    -75.0]),
+    ([-1, 0, 2, 2, 2], [     -59_641.0,      -11.0,     149.0,     25_543.0,    -11.0,     66.0]),
+    ([ 1, 0, 2, 0, 1], [     -51_613.0,      -42.0,     129.0,     26_366.0,      0.0,     78.0]),
+    ([-2, 0, 2, 0, 1], [      45_893.0,       50.0,      31.0,    -24_236.0,    -10.0,     20.0]),
+    ([ 0, 0, 0, 2, 0], [      63_384.0,       11.0,    -150.0,     -1_220.0,      0.0,     29.0]),
+    ([ 0, 0, 2, 2, 2], [     -38_571.0,       -1.0,     158.0,     16_452.0,    -11.0,     68.0]),
+    ([ 0,-2, 2,-2, 2], [      32_481.0,        0.0,       0.0,    -13_870.0,      0.0,      0.0]),
+    ([-2, 0, 0, 2, 0], [     -47_722.0,        0.0,     -18.0,        477.0,      0.0,    -25.0]),
+    ([ 2, 0, 2, 0, 2], [     -31_046.0,       -1.0,     131.0,     13_238.0,    -11.0,     59.0]),
+    ([ 1, 0, 2,-2, 2], [      28_593.0,        0.0,      -1.0,    -12_338.0,     10.0,     -3.0]),
+    ([-1, 0, 2, 0, 1], [      20_441.0,       21.0,      10.0,    -10_758.0,      0.0,     -3.0]),
+    ([ 2, 0, 0, 0, 0], [      29_243.0,        0.0,     -74.0,       -609.0,      0.0,     13.0]),
+    ([ 0, 0, 2, 0, 0], [      25_887.0,        0.0,     -66.0,       -550.0,      0.0,     11.0]),
+    ([ 0, 1, 0, 0, 1], [     -14_053.0,      -25.0,      79.0,      8_551.0,     -2.0,    -45.0]),
+    ([-1, 0, 0, 2, 1], [      15_164.0,       10.0,      11.0,     -8_001.0,      0.0,     -1.0]),
+    ([ 0, 2, 2,-2, 2], [     -15_794.0,       72.0,     -16.0,      6_850.0,    -42.0,     -5.0]),
+    ([ 0, 0,-2, 2, 0], [      21_783.0,        0.0,      13.0,       -167.0,      0.0,     13.0]),
+    ([ 1, 0, 0,-2, 1], [     -12_873.0,      -10.0,     -37.0,      6_953.0,      0.0,    -14.0]),
+    ([ 0,-1, 0, 0, 1], [     -12_654.0,       11.0,      63.0,      6_415.0,      0.0,     26.0]),
+    ([-1, 0, 2, 2, 1], [     -10_204.0,        0.0,      25.0,      5_222.0,      0.0,     15.0]),
+    ([ 0, 2, 0, 0, 0], [      16_707.0,      -85.0,     -10.0,        168.0,     -1.0,     10.0]),
+    ([ 1, 0, 2, 2, 2], [      -7_691.0,        0.0,      44.0,      3_268.0,      0.0,     19.0]),
+    ([-2, 0, 2, 0, 0], [     -11_024.0,        0.0,     -14.0,        104.0,      0.0,      2.0]),
+    ([ 0, 1, 2, 0, 2], [       7_566.0,      -21.0,     -11.0,     -3_250.0,      0.0,     -5.0]),
+    ([ 0, 0, 2, 2, 1], [      -6_637.0,      -11.0,      25.0,      3_353.0,      0.0,     14.0]),
+    ([ 0,-1, 2, 0, 2], [      -7_141.0,       21.0,       8.0,      3_070.0,      0.0,      4.0]),
+    ([ 0, 0, 0, 2, 1], [      -6_302.0,      -11.0,       2.0,      3_272.0,      0.0,      4.0]),
+    ([ 1, 0, 2,-2, 1], [       5_800.0,       10.0,       2.0,     -3_045.0,      0.0,     -1.0]),
+    ([ 2, 0, 2,-2, 2], [       6_443.0,        0.0,      -7.0,     -2_768.0,      0.0,     -4.0]),
+    ([-2, 0, 0, 2, 1], [      -5_774.0,      -11.0,     -15.0,      3_041.0,      0.0,     -5.0]),
+    ([ 2, 0, 2, 0, 1], [      -5_350.0,        0.0,      21.0,      2_695.0,      0.0,     12.0]),
+    ([ 0,-1, 2,-2, 1], [      -4_752.0,      -11.0,      -3.0,      2_719.0,      0.0,     -3.0]),
+    ([ 0, 0, 0,-2, 1], [      -4_940.0,      -11.0,     -21.0,      2_720.0,      0.0,     -9.0]),
+    ([-1,-1, 0, 2, 0], [       7_350.0,        0.0,      -8.0,        -51.0,      0.0,      4.0]),
+    ([ 2, 0, 0,-2, 1], [       4_065.0,        0.0,       6.0,     -2_206.0,      0.0,      1.0]),
+    ([ 1, 0, 0, 2, 0], [       6_579.0,        0.0,     -24.0,       -199.0,      0.0,      2.0]),
+    ([ 0, 1, 2,-2, 1], [       3_579.0,        0.0,       5.0,     -1_900.0,      0.0,      1.0]),
+    ([ 1,-1, 0, 0, 0], [       4_725.0,        0.0,      -6.0,        -41.0,      0.0,      3.0]),
+    ([-2, 0, 2, 0, 2], [      -3_075.0,        0.0,      -2.0,      1_313.0,      0.0,     -1.0]),
+    ([ 3, 0, 2, 0, 2], [      -2_904.0,        0.0,      15.0,      1_233.0,      0.0,      7.0]),
+    ([ 0,-1, 0, 2, 0], [       4_348.0,        0.0,     -10.0,        -81.0,      0.0,      2.0]),
+    ([ 1,-1, 2, 0, 2], [      -2_878.0,        0.0,       8.0,      1_232.0,      0.0,      4.0]),
+    ([ 0, 0, 0, 1, 0], [      -4_230.0,        0.0,       5.0,        -20.0,      0.0,     -2.0]),
+    ([-1,-1, 2, 2, 2], [      -2_819.0,        0.0,       7.0,      1_207.0,      0.0,      3.0]),
+    ([-1, 0, 2, 0, 0], [      -4_056.0,        0.0,       5.0,         40.0,      0.0,     -2.0]),
+    ([ 0,-1, 2, 2, 2], [      -2_647.0,        0.0,      11.0,      1_129.0,      0.0,      5.0]),
+    ([-2, 0, 0, 0, 1], [      -2_294.0,        0.0,     -10.0,      1_266.0,      0.0,     -4.0]),
+    ([ 1, 1, 2, 0, 2], [       2_481.0,        0.0,      -7.0,     -1_062.0,      0.0,     -3.0]),
+    ([ 2, 0, 0, 0, 1], [       2_179.0,        0.0,      -2.0,     -1_129.0,      0.0,     -2.0]),
+    ([-1, 1, 0, 1, 0], [       3_276.0,        0.0,       1.0,         -9.0,      0.0,      0.0]),
+    ([ 1, 1, 0, 0, 0], [      -3_389.0,        0.0,       5.0,         35.0,      0.0,     -2.0]),
+    ([ 1, 0, 2, 0, 0], [       3_339.0,        0.0,     -13.0,       -107.0,      0.0,      1.0]),
+    ([-1, 0, 2,-2, 1], [      -1_987.0,        0.0,      -6.0,      1_073.0,      0.0,     -2.0]),
+    ([ 1, 0, 0, 0, 2], [      -1_981.0,        0.0,       0.0,        854.0,      0.0,      0.0]),
+    ([-1, 0, 0, 1, 0], [       4_026.0,        0.0,    -353.0,       -553.0,      0.0,   -139.0]),
+    ([ 0, 0, 2, 1, 2], [       1_660.0,        0.0,      -5.0,       -710.0,      0.0,     -2.0]),
+    ([-1, 0, 2, 4, 2], [      -1_521.0,        0.0,       9.0,        647.0,      0.0,      4.0]),
+    ([-1, 1, 0, 1, 1], [       1_314.0,        0.0,       0.0,       -700.0,      0.0,      0.0]),
+    ([ 0,-2, 2,-2, 1], [      -1_283.0,        0.0,       0.0,        672.0,      0.0,      0.0]),
+    ([ 1, 0, 2, 2, 1], [      -1_331.0,        0.0,       8.0,        663.0,      0.0,      4.0]),
+    ([-2, 0, 2, 2, 2], [       1_383.0,        0.0,      -2.0,       -594.0,      0.0,     -2.0]),
+    ([-1, 0, 0, 0, 2], [       1_405.0,        0.0,       4.0,       -610.0,      0.0,      2.0]),
+    ([ 1, 1, 2,-2, 2], [       1_290.0,        0.0,       0.0,       -556.0,      0.0,      0.0]),
+];
+
+/// The largest periodic terms of the series for s + XY/2: the power of time they multiply, the
+/// multipliers of the Delaunay arguments, and the sine and cosine coefficients in arcseconds.
+#[rustfmt::skip]
+const CIO_LOCATOR_TERMS: [(usize, [i8; 5], f64, f64); 21] = [
+    (0, [0, 0, 0, 0, 1], -2_640.73e-6,  0.39e-6),
+    (0, [0, 0, 0, 0, 2],    -63.53e-6,  0.02e-6),
+    (0, [0, 0, 2,-2, 3],    -11.75e-6, -0.01e-6),
+    (0, [0, 0, 2,-2, 1],    -11.21e-6, -0.01e-6),
+    (0, [0, 0, 2,-2, 2],      4.57e-6,  0.00e-6),
+    (0, [0, 0, 2, 0, 3],     -2.02e-6,  0.00e-6),
+    (0, [0, 0, 2, 0, 1],     -1.98e-6,  0.00e-6),
+    (0, [0, 0, 0, 0, 3],      1.72e-6,  0.00e-6),
+    (0, [0, 1, 0, 0, 1],      1.41e-6,  0.01e-6),
+    (0, [0, 1, 0, 0,-1],      1.26e-6,  0.01e-6),
+    (0, [1, 0, 0, 0,-1],      0.63e-6,  0.00e-6),
+    (0, [1, 0, 0, 0, 1],      0.63e-6,  0.00e-6),
+    (1, [0, 0, 0, 0, 2],     -0.07e-6,  3.57e-6),
+    (1, [0, 0, 0, 0, 1],      1.73e-6, -0.03e-6),
+    (2, [0, 0, 0, 0, 1],    743.52e-6, -0.17e-6),
+    (2, [0, 0, 2,-2, 2],     56.91e-6,  0.06e-6),
+    (2, [0, 0, 2, 0, 2],      9.84e-6, -0.01e-6),
+    (2, [0, 0, 0, 0, 2],     -8.85e-6,  0.01e-6),
+    (2, [0, 1, 0, 0, 0],     -6.38e-6, -0.05e-6),
+    (2, [1, 0, 0, 0, 0],     -3.07e-6,  0.00e-6),
+    (3, [0, 0, 0, 0, 1],      0.30e-6, -23.42e-6),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use float_cmp::approx_eq;
+
+    use crate::nutation::julian_centuries;
+    use crate::JulianDay;
+
+    #[test]
+    fn test_nutation_2000b() {
+        // Reference values from the IAU SOFA routine iauNut00b.
+        let (longitude, obliquity) =
+            nutation_2000b(julian_centuries(JulianDay::new(2_400_000.5, 53_736.0)));
+        assert!(approx_eq!(
+            f64,
+            longitude,
+            -0.963255229114836e-5,
+            epsilon = 1e-13
+        ));
+        assert!(approx_eq!(
+            f64,
+            obliquity,
+            0.406319710662116e-4,
+            epsilon = 1e-13
+        ));
+    }
+
+    #[test]
+    fn test_intermediate_pole() {
+        // Reference values from the IAU SOFA routines iauXys06a, iauEo06a and iauS06, for the full IAU
+        // 2000A nutation, which IAU 2000B follows to within a milliarcsecond.
+        let t = julian_centuries(JulianDay::new(2_400_000.5, 53_736.0));
+        let pole = IntermediatePole::at(t, 0.0, 0.0);
+        assert!(approx_eq!(
+            f64,
+            pole.x,
+            0.579130848283529e-3,
+            epsilon = 2e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            pole.y,
+            0.402058009945402e-4,
+            epsilon = 2e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            pole.equation_of_origins,
+            -0.133288237194183e-2,
+            epsilon = 2e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            cio_locator(t, 0.579130848670601e-3, 0.402057981673296e-4),
+            -0.122003221307646e-7,
+            epsilon = 1e-11
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+//! Conversions between the crate's vectors and rotations and those of `nalgebra` and `glam`.
+//!
+//! The components of a [FrameVector] are in km or km/s, and carry over unchanged. The frame is not
+//! known to the other libraries, so a frame must be chosen when converting back.
+
+use crate::{Frame, FrameRotation, FrameVector};
+
+#[cfg(feature = "nalgebra")]
+impl<F: Frame> From<FrameVector<F>> for nalgebra::Vector3<f64> {
+    fn from(v: FrameVector<F>) -> Self {
+        nalgebra::Vector3::from(*v)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<F: Frame> From<nalgebra::Vector3<f64>> for FrameVector<F> {
+    fn from(v: nalgebra::Vector3<f64>) -> Self {
+        FrameVector::new(v.into())
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl<F: Frame, G: Frame> From<FrameRotation<F, G>> for nalgebra::Rotation3<f64> {
+    fn from(rotation: FrameRotation<F, G>) -> Self {
+        let m = rotation.matrix();
+        nalgebra::Rotation3::from_matrix_unchecked(nalgebra::Matrix3::from_fn(|i, j| m[i][j]))
+    }
+}
+
+#[cfg(feature = "glam")]
+impl<F: Frame> From<FrameVector<F>> for glam::DVec3 {
+    fn from(v: FrameVector<F>) -> Self {
+        glam::DVec3::from_array(*v)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl<F: Frame> From<glam::DVec3> for FrameVector<F> {
+    fn from(v: glam::DVec3) -> Self {
+        FrameVector::new(v.to_array())
+    }
+}
+
+#[cfg(feature = "glam")]
+impl<F: Frame, G: Frame> From<FrameRotation<F, G>> for glam::DMat3 {
+    fn from(rotation: FrameRotation<F, G>) -> Self {
+        // glam matrices are built from columns.
+        glam::DMat3::from_cols_array_2d(&rotation.inverse().matrix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use float_cmp::approx_eq;
+
+    use crate::{CelestialPoleOffsets, FrameRotation, FrameVector, Gcrf, LeapSecondTable, Teme};
+
+    fn rotation() -> FrameRotation<Teme, Gcrf> {
+        let epoch = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        FrameRotation::inertial(
+            epoch,
+            &LeapSecondTable::default(),
+            &CelestialPoleOffsets::default(),
+        )
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_nalgebra() {
+        let v = FrameVector::<Teme>::new([7000.0, -1200.0, 300.0]);
+        let n: nalgebra::Vector3<f64> = v.into();
+        assert_eq!(n, nalgebra::Vector3::new(7000.0, -1200.0, 300.0));
+        assert_eq!(FrameVector::<Teme>::from(n), v);
+
+        let rotation = rotation();
+        let rotated = nalgebra::Rotation3::from(rotation) * n;
+        let expected = rotation.apply(&v);
+        for i in 0..3 {
+            assert!(approx_eq!(f64, rotated[i], expected[i], epsilon = 1e-9));
+        }
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam() {
+        let v = FrameVector::<Teme>::new([7000.0, -1200.0, 300.0]);
+        let g: glam::DVec3 = v.into();
+        assert_eq!(g, glam::DVec3::new(7000.0, -1200.0, 300.0));
+        assert_eq!(FrameVector::<Teme>::from(g), v);
+
+        let rotation = rotation();
+        let rotated = glam::DMat3::from(rotation) * g;
+        let expected = rotation.apply(&v);
+        for i in 0..3 {
+            assert!(approx_eq!(f64, rotated[i], expected[i], epsilon = 1e-9));
+        }
+    }
+}
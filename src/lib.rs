@@ -1,53 +1,174 @@
 use chrono::prelude::*;
 use chrono::DateTime;
 use thiserror::Error;
-use uom::si::{angle, angular_velocity::radian_per_second, f64::*, length::kilometer};
-
+use uom::si::{
+    angle, angular_acceleration::radian_per_second_squared, angular_jerk::radian_per_second_cubed,
+    angular_velocity::radian_per_second, f64::*, length::kilometer, velocity::kilometer_per_second,
+};
+
+mod catalog;
+mod earth_orientation;
+mod frames;
+mod geodetic;
+mod iau2006;
+#[cfg(any(feature = "nalgebra", feature = "glam"))]
+mod interop;
+mod nutation;
+mod observer;
+mod omm;
+mod passes;
+#[cfg(feature = "serde")]
+mod serialization;
 mod sgp4_sys;
+mod sidereal;
+mod time;
+mod tle_format;
 #[cfg(feature = "tlegen")]
 mod tlegen;
 
+pub use catalog::{CatalogReader, NamedTle};
+pub use earth_orientation::{EarthOrientation, EarthOrientationParameters};
+pub use frames::{
+    CelestialPoleOffsets, Frame, FrameRotation, FrameVector, Gcrf, InertialFrame, Itrf, MeanOfDate,
+    PrecessionNutationModel, Teme, Topocentric, TrueOfDate, J2000,
+};
+pub use geodetic::{Ellipsoid, GeodeticPosition};
+pub use observer::{LookAngles, Observer};
+pub use omm::OrbitMeanElements;
+pub use passes::Pass;
+#[cfg(feature = "serde")]
+pub use serialization::as_omm;
+pub use sidereal::{
+    EarthRotationAngle, GreenwichApparentSiderealTime, GreenwichMeanSiderealTime, LocalSiderealTime,
+};
+pub use time::{Epoch, GpsWeekTime, LeapSecondTable, TimeScale, Ut1Source};
+pub use tle_format::{ParseMode, TleEpoch, TleParseError};
+
 #[derive(Debug, Error, PartialEq)]
 pub enum Error {
     #[error("TLE was malformed: {0}")]
-    MalformedTwoLineElement(String),
+    MalformedTwoLineElement(#[from] TleParseError),
+    #[error("TLE cannot be represented in text form: {0}")]
+    UnrepresentableTwoLineElement(String),
+    #[error("OMM was malformed: {0}")]
+    MalformedOrbitMeanElements(String),
     #[error("{0}")]
     UnknownError(String),
     #[error(transparent)]
     PropagationError(#[from] sgp4_sys::Error),
     #[error("Optimization error: {0}")]
     OptimizationError(String),
+    #[error("Catalog entry starting at line {line} was malformed: {error}")]
+    MalformedCatalogEntry { line: usize, error: Box<Error> },
+    #[error("I/O error: {0}")]
+    IoError(String),
+    #[error("Epoch was malformed: {0}")]
+    MalformedEpoch(String),
+    #[error("Leap second table was malformed: {0}")]
+    MalformedLeapSecondTable(String),
+    #[error("Earth orientation data was malformed: {0}")]
+    MalformedEarthOrientationData(String),
 }
 
 type Result<T> = std::result::Result<T, Error>;
 
-/// A state vector, in the TEME-ECI coordinate frame, for an orbiting body.
+/// A state vector for an orbiting body, in the reference frame `F`.
 ///
 /// To obtain the state of an object at a specific time, use the propagation functions provided by
-/// [TwoLineElement].
+/// [TwoLineElement], which give states in the TEME-ECI coordinate frame. Conversions to other frames
+/// are provided for the frames in which they are defined.
 #[derive(Debug, Clone, Copy)]
-pub struct StateVector {
+pub struct StateVector<F: Frame = Teme> {
     pub epoch: DateTime<Utc>,
 
     /// The satellite position in km.
-    pub position: [f64; 3],
+    pub position: FrameVector<F>,
 
     /// The satellite velocity in km/s.
-    pub velocity: [f64; 3],
+    pub velocity: FrameVector<F>,
+
+    /// The osculating orbital elements, in inertial frames.
+    pub coe: F::Elements,
 
-    pub coe: ClassicalOrbitalElements,
+    /// The gravity model whose gravitational parameter gives the orbital elements. It is kept
+    /// through conversions between frames.
+    pub gravity_model: GravityModel,
 }
 
 impl StateVector {
     pub fn new(epoch: DateTime<Utc>, position: [f64; 3], velocity: [f64; 3]) -> Self {
+        Self::with_gravity_model(epoch, position, velocity, GravityModel::default())
+    }
+
+    /// Create a state vector whose classical orbital elements are derived using the gravitational
+    /// parameter of the given gravity model.
+    pub fn with_gravity_model(
+        epoch: DateTime<Utc>,
+        position: [f64; 3],
+        velocity: [f64; 3],
+        gravity_model: GravityModel,
+    ) -> Self {
+        Self::from_vectors_with_gravity_model(
+            epoch,
+            position.into(),
+            velocity.into(),
+            gravity_model,
+        )
+    }
+
+    /// Create a state vector from a position and velocity with units.
+    pub fn from_quantities(
+        epoch: DateTime<Utc>,
+        position: [Length; 3],
+        velocity: [Velocity; 3],
+    ) -> Self {
+        Self::new(
+            epoch,
+            position.map(|p| p.get::<kilometer>()),
+            velocity.map(|v| v.get::<kilometer_per_second>()),
+        )
+    }
+}
+
+impl<F: Frame> StateVector<F> {
+    /// Create a state vector in the frame of its position and velocity.
+    pub fn from_vectors(
+        epoch: DateTime<Utc>,
+        position: FrameVector<F>,
+        velocity: FrameVector<F>,
+    ) -> Self {
+        Self::from_vectors_with_gravity_model(epoch, position, velocity, GravityModel::default())
+    }
+
+    /// Create a state vector in the frame of its position and velocity, whose orbital elements are
+    /// derived using the gravitational parameter of the given gravity model.
+    pub fn from_vectors_with_gravity_model(
+        epoch: DateTime<Utc>,
+        position: FrameVector<F>,
+        velocity: FrameVector<F>,
+        gravity_model: GravityModel,
+    ) -> Self {
         Self {
             epoch,
             position,
             velocity,
-            coe: sgp4_sys::to_classical_elements(&position, &velocity).into(),
+            coe: F::elements(&position, &velocity, gravity_model),
+            gravity_model,
         }
     }
 
+    /// The position, with units.
+    pub fn position_quantities(&self) -> [Length; 3] {
+        self.position.lengths()
+    }
+
+    /// The velocity, with units.
+    pub fn velocity_quantities(&self) -> [Velocity; 3] {
+        self.velocity.velocities()
+    }
+}
+
+impl<F: InertialFrame> StateVector<F> {
     pub fn semilatus_rectum(&self) -> Length {
         self.coe.semilatus_rectum
     }
@@ -143,14 +264,81 @@ impl From<sgp4_sys::ClassicalOrbitalElements> for ClassicalOrbitalElements {
     }
 }
 
-impl From<StateVector> for ClassicalOrbitalElements {
-    fn from(sv: StateVector) -> Self {
+impl<F: InertialFrame> From<StateVector<F>> for ClassicalOrbitalElements {
+    fn from(sv: StateVector<F>) -> Self {
         sv.coe
     }
 }
 
+/// The six SGP4 mean orbital elements of a TLE.
+///
+/// Unlike [ClassicalOrbitalElements], which are osculating values for a single instant, these are
+/// the averaged elements which SGP4 propagates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeanElements {
+    pub inclination: Angle,
+    pub raan: Angle,
+    pub eccentricity: f64,
+    pub argument_of_perigee: Angle,
+    pub mean_anomaly: Angle,
+    /// The Kozai mean motion, as written in a TLE.
+    pub mean_motion: AngularVelocity,
+}
+
+/// The set of Earth gravitational constants used by SGP4.
+///
+/// Element sets are fitted by their producers using WGS-72, so that model gives the most
+/// consistent results; WGS-84 is the default for backwards compatibility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GravityModel {
+    /// WGS-72 with the low-precision constants of the original Spacetrack Report #3.
+    Wgs72Old,
+    Wgs72,
+    #[default]
+    Wgs84,
+}
+
+impl From<GravityModel> for sgp4_sys::GravitationalConstant {
+    fn from(model: GravityModel) -> Self {
+        match model {
+            GravityModel::Wgs72Old => sgp4_sys::GravitationalConstant::Wgs72Old,
+            GravityModel::Wgs72 => sgp4_sys::GravitationalConstant::Wgs72,
+            GravityModel::Wgs84 => sgp4_sys::GravitationalConstant::Wgs84,
+        }
+    }
+}
+
+/// The SGP4 operation mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OperationMode {
+    /// Reproduce the behaviour of the operational AFSPC code, for bit-compatibility with it.
+    Afspc,
+    /// Use the improved sidereal time and angle handling of the reference implementation.
+    #[default]
+    Improved,
+}
+
+impl From<OperationMode> for sgp4_sys::OperationMode {
+    fn from(mode: OperationMode) -> Self {
+        match mode {
+            OperationMode::Afspc => sgp4_sys::OperationMode::AirForceSpaceCenter,
+            OperationMode::Improved => sgp4_sys::OperationMode::Improved,
+        }
+    }
+}
+
+/// Options controlling how SGP4 is initialized and run for a [TwoLineElement].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PropagationOptions {
+    pub gravity_model: GravityModel,
+    pub operation_mode: OperationMode,
+}
+
 const TLE_LINE_LENGTH: usize = 69;
 
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND: f64 = 2.0 * std::f64::consts::PI / SECONDS_PER_DAY;
+
 /// A parsed, valid Two Line Element data set which can be used for orbital propagation.
 ///
 /// Internally this uses SGP4's own structure representation. Various fields which are useful for
@@ -158,45 +346,70 @@ const TLE_LINE_LENGTH: usize = 69;
 /// which allow access to the values and direct modification of the underlying orbital element set
 /// in a type-safe manner. The `uom` crate provides dimensional analysis to help avoid
 /// unit-of-measure errors which can otherwise be quite difficult to detect.
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct TwoLineElement {
     elements: sgp4_sys::OrbitalElementSet,
+    fields: tle_format::TleFields,
+    options: PropagationOptions,
+    parse_mode: Option<ParseMode>,
 }
 
 impl TwoLineElement {
-    /// Create a validated TwoLineElement from a string.
+    /// Create a validated TwoLineElement from a string, using the default propagation options.
+    ///
+    /// The lines are checked in [ParseMode::Lenient], so their checksums are not verified. Use
+    /// [TwoLineElement::parse] with [ParseMode::Strict] to reject lines with a wrong checksum.
     pub fn new(line1: &str, line2: &str) -> Result<TwoLineElement> {
-        let line1 = line1.trim();
-        let line2 = line2.trim();
-
-        if line1.len() != TLE_LINE_LENGTH {
-            return Err(Error::MalformedTwoLineElement(format!(
-                "Line 1 is the wrong length. Expected {}, but got {}\n{}",
-                TLE_LINE_LENGTH,
-                line1.len(),
-                line1
-            )));
-        }
+        TwoLineElement::new_with_options(line1, line2, PropagationOptions::default())
+    }
 
-        if line2.len() != TLE_LINE_LENGTH {
-            return Err(Error::MalformedTwoLineElement(format!(
-                "Line 2 is the wrong length. Expected {}, but got {}\n{}",
-                TLE_LINE_LENGTH,
-                line2.len(),
-                line2
-            )));
-        }
+    /// Create a validated TwoLineElement from a string, propagated with the given options.
+    pub fn new_with_options(
+        line1: &str,
+        line2: &str,
+        options: PropagationOptions,
+    ) -> Result<TwoLineElement> {
+        TwoLineElement::parse(line1, line2, ParseMode::Lenient, options)
+    }
 
-        let elements = sgp4_sys::to_orbital_elements(
-            line1,
-            line2,
+    /// Create a validated TwoLineElement from a string, checking the text according to the given
+    /// parse mode.
+    ///
+    /// The mode actually needed to accept the lines is available from
+    /// [TwoLineElement::parse_mode], so that callers using [ParseMode::Lenient] can tell which
+    /// element sets would also have passed the strict checks.
+    pub fn parse(
+        line1: &str,
+        line2: &str,
+        mode: ParseMode,
+        options: PropagationOptions,
+    ) -> Result<TwoLineElement> {
+        let accepted_mode = strictest_parse_mode(line1, line2, mode);
+        let line1 = tle_format::normalize_line(line1, 1, mode)?;
+        let line2 = tle_format::normalize_line(line2, 2, mode)?;
+
+        let fields = tle_format::TleFields::parse(&line1, &line2)?;
+
+        // The underlying parser only understands numeric catalog numbers, so Alpha-5 fields are
+        // replaced with a placeholder and the decoded value is restored afterwards.
+        let (line1, line2) = (numeric_catalog_field(&line1), numeric_catalog_field(&line2));
+
+        let mut elements = sgp4_sys::to_orbital_elements(
+            &line1,
+            &line2,
             sgp4_sys::RunType::Verification,
-            sgp4_sys::OperationMode::Improved,
-            sgp4_sys::GravitationalConstant::Wgs84,
+            options.operation_mode.into(),
+            options.gravity_model.into(),
         )
-        .map_err(|e| Error::MalformedTwoLineElement(e.to_string()))?;
-
-        Ok(TwoLineElement { elements })
+        .map_err(TleParseError::RejectedElements)?;
+        elements.catalog_number = fields.catalog_number.into();
+
+        Ok(TwoLineElement {
+            elements,
+            fields,
+            options,
+            parse_mode: Some(accepted_mode),
+        })
     }
 
     /// Create a TwoLineElement from a string containing both lines, and optionally a header line.
@@ -211,39 +424,370 @@ impl TwoLineElement {
             } else if ls.len() == 2 {
                 ls
             } else {
-                return Err(Error::MalformedTwoLineElement(format!(
-                    "Expected two lines, got {}",
-                    ls.len()
-                )));
+                return Err(TleParseError::WrongLineCount(ls.len()).into());
             }
         };
-        TwoLineElement::new(lines[0], lines[1])
+        TwoLineElement::new(lines[0].trim(), lines[1].trim())
+    }
+
+    /// Create a TwoLineElement directly from mean elements.
+    ///
+    /// SGP4 is initialized from the given values at full double precision, without formatting
+    /// them as TLE text. The remaining TLE metadata is set to defaults: unclassified, no
+    /// international designator, element set number 999 and revolution number zero.
+    pub fn from_mean_elements(
+        epoch: DateTime<Utc>,
+        elements: MeanElements,
+        bstar: f64,
+        ndot: AngularAcceleration,
+        nddot: AngularJerk,
+        catalog_number: u32,
+    ) -> Result<TwoLineElement> {
+        TwoLineElement::from_mean_elements_with_options(
+            epoch,
+            elements,
+            bstar,
+            ndot,
+            nddot,
+            catalog_number,
+            PropagationOptions::default(),
+        )
+    }
+
+    /// Create a TwoLineElement directly from mean elements, propagated with the given options.
+    pub fn from_mean_elements_with_options(
+        epoch: DateTime<Utc>,
+        elements: MeanElements,
+        bstar: f64,
+        ndot: AngularAcceleration,
+        nddot: AngularJerk,
+        catalog_number: u32,
+        options: PropagationOptions,
+    ) -> Result<TwoLineElement> {
+        let fields = tle_format::TleFields {
+            catalog_number,
+            classification: 'U',
+            international_designator: String::new(),
+            epoch,
+            mean_motion_dot: ndot.get::<radian_per_second_squared>() * SECONDS_PER_DAY
+                / REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND
+                / 2.0,
+            mean_motion_ddot: nddot.get::<radian_per_second_cubed>() * SECONDS_PER_DAY.powi(2)
+                / REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND
+                / 6.0,
+            bstar,
+            ephemeris_type: 0,
+            element_set_number: 999,
+            inclination: elements.inclination.get::<angle::degree>(),
+            raan: elements.raan.get::<angle::degree>(),
+            eccentricity: elements.eccentricity,
+            argument_of_perigee: elements.argument_of_perigee.get::<angle::degree>(),
+            mean_anomaly: elements.mean_anomaly.get::<angle::degree>(),
+            mean_motion: elements.mean_motion.get::<radian_per_second>()
+                / REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND,
+            revolution_number: 0,
+            zero_exponent_sign: '-',
+        };
+
+        TwoLineElement::initialize(fields, options)
+    }
+
+    /// Create a TwoLineElement from the mean elements of an Orbit Mean-Elements Message.
+    ///
+    /// SGP4 is initialized directly from the message values, so no precision is lost to the fixed
+    /// width columns of the TLE format.
+    pub fn from_omm(omm: &OrbitMeanElements) -> Result<TwoLineElement> {
+        TwoLineElement::from_omm_with_options(omm, PropagationOptions::default())
+    }
+
+    /// Create a TwoLineElement from an Orbit Mean-Elements Message, propagated with the given
+    /// options.
+    pub fn from_omm_with_options(
+        omm: &OrbitMeanElements,
+        options: PropagationOptions,
+    ) -> Result<TwoLineElement> {
+        let fields = tle_format::TleFields::from(omm);
+
+        TwoLineElement::initialize(fields, options)
+    }
+
+    /// Initialize SGP4 from the fields and options.
+    fn initialize(
+        fields: tle_format::TleFields,
+        options: PropagationOptions,
+    ) -> Result<TwoLineElement> {
+        let elements = sgp4_sys::init_orbital_elements(
+            &fields.to_mean_elements(),
+            options.operation_mode.into(),
+            options.gravity_model.into(),
+        )?;
+
+        Ok(TwoLineElement {
+            elements,
+            fields,
+            options,
+            parse_mode: None,
+        })
+    }
+
+    /// Re-initialize the TwoLineElement to propagate with different options.
+    ///
+    /// SGP4 is initialized a second time, so it is better to give the options at construction
+    /// with [TwoLineElement::new_with_options], [TwoLineElement::from_omm_with_options] or
+    /// [TwoLineElement::from_mean_elements_with_options]. This is intended for changing the options
+    /// of an existing element set.
+    pub fn with_options(self, options: PropagationOptions) -> Result<TwoLineElement> {
+        Ok(TwoLineElement {
+            parse_mode: self.parse_mode,
+            ..TwoLineElement::initialize(self.fields, options)?
+        })
+    }
+
+    /// Get the options used to initialize and propagate this TwoLineElement.
+    pub fn options(&self) -> PropagationOptions {
+        self.options
+    }
+
+    /// Get the strictest parse mode which accepts the text of this TwoLineElement.
+    ///
+    /// After an edit this describes the lines written by [TwoLineElement::to_lines], rather than
+    /// the text originally read. This is `None` for element sets which were not parsed from text,
+    /// or whose edited fields can no longer be written as text.
+    pub fn parse_mode(&self) -> Option<ParseMode> {
+        self.parse_mode
+    }
+
+    /// Write the TwoLineElement out as its two lines of text, with checksums.
+    ///
+    /// A parsed TLE is reproduced byte-for-byte, provided it used the standard column layout.
+    /// This fails if a field cannot be represented in its columns, such as a catalog number above
+    /// the Alpha-5 range.
+    pub fn to_lines(&self) -> Result<[String; 2]> {
+        self.fields.format()
+    }
+
+    /// The mean elements and metadata of the TwoLineElement as an Orbit Mean-Elements Message.
+    ///
+    /// A TLE carries no object name, so it is left empty.
+    pub fn to_omm(&self) -> OrbitMeanElements {
+        OrbitMeanElements::from(&self.fields)
+    }
+
+    /// Get the satellite catalog number.
+    ///
+    /// Alpha-5 catalog numbers are decoded, so this returns the full numeric ID, e.g. 100001 for
+    /// `A0001`.
+    pub fn catalog_number(&self) -> u32 {
+        self.elements.catalog_number as u32
     }
 
     /// Get the epoch of a TwoLineElement.
+    ///
+    /// This is exact to the nanosecond; the eight decimal places of a TLE epoch resolve 864µs.
     pub fn epoch(&self) -> Result<DateTime<Utc>> {
-        Ok(self.elements.epoch())
+        Ok(self.fields.epoch)
+    }
+
+    /// Get the six mean orbital elements.
+    pub fn mean_elements(&self) -> MeanElements {
+        MeanElements {
+            inclination: self.inclination(),
+            raan: self.raan(),
+            eccentricity: self.eccentricity(),
+            argument_of_perigee: self.argument_of_perigee(),
+            mean_anomaly: self.mean_anomaly(),
+            mean_motion: self.kozai_mean_motion(),
+        }
     }
 
+    /// Get the Brouwer mean motion which SGP4 recovers from the TLE value when it is initialized.
+    ///
+    /// Use [TwoLineElement::kozai_mean_motion] for the value as written in the TLE.
     pub fn mean_motion(&self) -> AngularVelocity {
         AngularVelocity::new::<radian_per_second>(self.elements.mean_motion() / 60.)
     }
 
+    /// Get the mean motion as written in the TLE, which is a Kozai mean motion.
+    pub fn kozai_mean_motion(&self) -> AngularVelocity {
+        AngularVelocity::new::<radian_per_second>(
+            self.fields.mean_motion * REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND,
+        )
+    }
+
+    pub fn inclination(&self) -> Angle {
+        Angle::new::<angle::degree>(self.fields.inclination)
+    }
+
+    /// Get the right ascension of the ascending node.
+    pub fn raan(&self) -> Angle {
+        Angle::new::<angle::degree>(self.fields.raan)
+    }
+
+    pub fn eccentricity(&self) -> f64 {
+        self.fields.eccentricity
+    }
+
+    pub fn argument_of_perigee(&self) -> Angle {
+        Angle::new::<angle::degree>(self.fields.argument_of_perigee)
+    }
+
+    pub fn mean_anomaly(&self) -> Angle {
+        Angle::new::<angle::degree>(self.fields.mean_anomaly)
+    }
+
+    /// Get the SGP4 drag term, in inverse Earth radii.
+    pub fn bstar(&self) -> f64 {
+        self.fields.bstar
+    }
+
+    /// Get the first derivative of mean motion.
+    ///
+    /// Note that the TLE format stores half of this value.
+    pub fn mean_motion_dot(&self) -> AngularAcceleration {
+        AngularAcceleration::new::<radian_per_second_squared>(
+            2.0 * self.fields.mean_motion_dot * REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND
+                / SECONDS_PER_DAY,
+        )
+    }
+
+    /// Get the second derivative of mean motion.
+    ///
+    /// Note that the TLE format stores one sixth of this value.
+    pub fn mean_motion_ddot(&self) -> AngularJerk {
+        AngularJerk::new::<radian_per_second_cubed>(
+            6.0 * self.fields.mean_motion_ddot * REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND
+                / SECONDS_PER_DAY.powi(2),
+        )
+    }
+
+    /// Get the security classification, usually `U` for unclassified.
+    pub fn classification(&self) -> char {
+        self.fields.classification
+    }
+
+    /// Get the international designator in its TLE form, e.g. `98067A` for launch 1998-067A.
+    ///
+    /// This is empty if the designator was not given.
+    pub fn international_designator(&self) -> &str {
+        &self.fields.international_designator
+    }
+
+    pub fn ephemeris_type(&self) -> u8 {
+        self.fields.ephemeris_type
+    }
+
+    pub fn element_set_number(&self) -> u32 {
+        self.fields.element_set_number
+    }
+
+    /// Get the number of revolutions the object had completed at epoch.
+    pub fn revolution_number(&self) -> u32 {
+        self.fields.revolution_number
+    }
+
+    /// Set the epoch, keeping all other elements unchanged.
+    pub fn set_epoch(&mut self, epoch: DateTime<Utc>) -> Result<()> {
+        self.update(|fields| fields.epoch = epoch)
+    }
+
+    pub fn set_inclination(&mut self, inclination: Angle) -> Result<()> {
+        self.update(|fields| fields.inclination = inclination.get::<angle::degree>())
+    }
+
+    /// Set the right ascension of the ascending node.
+    pub fn set_raan(&mut self, raan: Angle) -> Result<()> {
+        self.update(|fields| fields.raan = raan.get::<angle::degree>())
+    }
+
+    pub fn set_eccentricity(&mut self, eccentricity: f64) -> Result<()> {
+        self.update(|fields| fields.eccentricity = eccentricity)
+    }
+
+    pub fn set_argument_of_perigee(&mut self, argument_of_perigee: Angle) -> Result<()> {
+        self.update(|fields| {
+            fields.argument_of_perigee = argument_of_perigee.get::<angle::degree>()
+        })
+    }
+
+    pub fn set_mean_anomaly(&mut self, mean_anomaly: Angle) -> Result<()> {
+        self.update(|fields| fields.mean_anomaly = mean_anomaly.get::<angle::degree>())
+    }
+
+    /// Set the mean motion as written in the TLE, the counterpart of
+    /// [TwoLineElement::kozai_mean_motion].
+    pub fn set_kozai_mean_motion(&mut self, mean_motion: AngularVelocity) -> Result<()> {
+        self.update(|fields| {
+            fields.mean_motion =
+                mean_motion.get::<radian_per_second>() / REVOLUTIONS_PER_DAY_TO_RADIANS_PER_SECOND
+        })
+    }
+
+    /// Set the SGP4 drag term, in inverse Earth radii.
+    pub fn set_bstar(&mut self, bstar: f64) -> Result<()> {
+        self.update(|fields| fields.bstar = bstar)
+    }
+
+    /// Apply an edit to the fields and re-initialize SGP4 from them.
+    ///
+    /// If the edited elements are rejected by SGP4, the TwoLineElement is left unchanged.
+    fn update(&mut self, edit: impl FnOnce(&mut tle_format::TleFields)) -> Result<()> {
+        let mut fields = self.fields.clone();
+        edit(&mut fields);
+
+        // The edited fields are written out afresh, so the original text no longer applies.
+        let parse_mode = self.parse_mode.and_then(|_| {
+            let [line1, line2] = fields.format().ok()?;
+            Some(strictest_parse_mode(&line1, &line2, ParseMode::Lenient))
+        });
+        *self = TwoLineElement {
+            parse_mode,
+            ..TwoLineElement::initialize(fields, self.options)?
+        };
+        Ok(())
+    }
+
     /// Propagate a TwoLineElement to the given time to obtain a state vector for the object.
     pub fn propagate_to(&self, t: DateTime<Utc>) -> Result<StateVector> {
-        let tle_epoch = self.elements.epoch();
+        let tle_epoch = self.fields.epoch;
         // TODO: determine correct behaviour for negative prop
         // assert!(t >= tle_epoch);
 
-        let min_since_epoch = (t - tle_epoch).num_milliseconds() as f64 / 60_000.;
+        let since_epoch = t - tle_epoch;
+        let min_since_epoch =
+            (since_epoch.num_seconds() as f64 + since_epoch.subsec_nanos() as f64 * 1e-9) / 60.;
 
-        let (r, v) = sgp4_sys::run_sgp4(
-            self.elements,
-            sgp4_sys::GravitationalConstant::Wgs84,
-            min_since_epoch,
-        )?;
+        let gravity_model = self.options.gravity_model;
+        let (r, v) = sgp4_sys::run_sgp4(self.elements, gravity_model.into(), min_since_epoch)?;
+
+        Ok(StateVector::with_gravity_model(
+            t,
+            r.to_owned(),
+            v.to_owned(),
+            gravity_model,
+        ))
+    }
+}
 
-        Ok(StateVector::new(t, r.to_owned(), v.to_owned()))
+/// The strictest parse mode which accepts a pair of lines, given a mode which is known to.
+fn strictest_parse_mode(line1: &str, line2: &str, mode: ParseMode) -> ParseMode {
+    let strict = [(line1, 1), (line2, 2)]
+        .iter()
+        .all(|(text, line)| tle_format::normalize_line(text, *line, ParseMode::Strict).is_ok());
+    if strict {
+        ParseMode::Strict
+    } else {
+        mode
+    }
+}
+
+/// Replace an Alpha-5 catalog number in a TLE line with zeros.
+fn numeric_catalog_field(line: &str) -> String {
+    let columns = tle_format::CATALOG_NUMBER_COLUMNS;
+    match line.get(columns.clone()) {
+        Some(field) if field.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+            format!("{}00000{}", &line[..columns.start], &line[columns.end..])
+        }
+        _ => line.to_owned(),
     }
 }
 
@@ -251,36 +795,115 @@ impl TwoLineElement {
 ///
 /// This is the number of days since the start of the Julian astronomical calendar in 4713 BC, used
 /// to provide a consistent time reference for astronomical calculations.
-pub struct JulianDay(f64);
+///
+/// The date is held in two parts, the Julian date of the preceding midnight and the fraction of the
+/// day since then, as a single `f64` cannot resolve better than tens of microseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JulianDay {
+    day: f64,
+    fraction: f64,
+}
+
+/// The Julian date at which Modified Julian Dates begin, 17 Nov 1858 00:00.
+const MODIFIED_JULIAN_DAY_OFFSET: f64 = 2_400_000.5;
+
+const NANOSECONDS_PER_DAY: f64 = SECONDS_PER_DAY * 1e9;
+
+impl JulianDay {
+    /// Create a Julian date from two parts which sum to it, such as a day and a fraction of a day.
+    pub fn new(day: f64, fraction: f64) -> Self {
+        // Normalize so that the first part is the preceding midnight, which keeps the parts of
+        // equal dates equal.
+        let midnight = (day - 0.5).floor() + 0.5;
+        let fraction = (day - midnight) + fraction;
+        let whole_days = fraction.floor();
+        JulianDay {
+            day: midnight + whole_days,
+            fraction: fraction - whole_days,
+        }
+    }
+
+    /// Create a Julian date from a Modified Julian Date.
+    pub fn from_modified(mjd: f64) -> Self {
+        JulianDay::new(MODIFIED_JULIAN_DAY_OFFSET, mjd)
+    }
+
+    /// Get the Julian date as a single number of days.
+    ///
+    /// This loses precision; use [JulianDay::parts] where it matters.
+    pub fn value(&self) -> f64 {
+        self.day + self.fraction
+    }
+
+    /// Get the Julian date of the preceding midnight, and the fraction of the day since then.
+    pub fn parts(&self) -> (f64, f64) {
+        (self.day, self.fraction)
+    }
+
+    /// Get the Modified Julian Date, the number of days since 17 Nov 1858 00:00.
+    pub fn modified(&self) -> f64 {
+        (self.day - MODIFIED_JULIAN_DAY_OFFSET) + self.fraction
+    }
+}
 
 impl From<DateTime<Utc>> for JulianDay {
     fn from(d: DateTime<Utc>) -> Self {
-        JulianDay(sgp4_sys::datetime_to_julian_day(d))
+        let (day, fraction) = sgp4_sys::datetime_to_split_julian_day(d);
+        JulianDay { day, fraction }
     }
 }
 
-impl From<JulianDay> for DateTime<Utc> {
-    fn from(jd: JulianDay) -> Self {
-        sgp4_sys::julian_day_to_datetime(jd.0)
+/// Gives the Julian date of the epoch's clock reading, in its own time scale.
+impl From<Epoch> for JulianDay {
+    fn from(epoch: Epoch) -> Self {
+        JulianDay::from(epoch.time().and_utc())
     }
 }
 
-/// Wrapper type representing the angular form of Greenwich Mean Sidereal Time.
-///
-/// This is primarily used to account for the Earth's rotation during conversion between fixed and
-/// inertial coordinate frames. Note that this is an angle measured in radians, and not a "time" as
-/// such. The value may range from 0 to 2π.
-pub struct GreenwichMeanSiderealTime(f64);
+impl std::ops::Add<chrono::Duration> for JulianDay {
+    type Output = JulianDay;
 
-impl GreenwichMeanSiderealTime {
-    pub fn as_radians(&self) -> f64 {
-        self.0
+    fn add(self, duration: chrono::Duration) -> JulianDay {
+        let days = duration.num_days();
+        let rest = duration - chrono::Duration::days(days);
+        JulianDay::new(
+            self.day + days as f64,
+            self.fraction + rest.num_nanoseconds().unwrap() as f64 / NANOSECONDS_PER_DAY,
+        )
     }
 }
 
-impl From<DateTime<Utc>> for GreenwichMeanSiderealTime {
-    fn from(d: DateTime<Utc>) -> Self {
-        GreenwichMeanSiderealTime(sgp4_sys::datetime_to_gstime(d))
+impl std::ops::Sub<chrono::Duration> for JulianDay {
+    type Output = JulianDay;
+
+    fn sub(self, duration: chrono::Duration) -> JulianDay {
+        self + -duration
+    }
+}
+
+impl std::ops::Sub for JulianDay {
+    type Output = chrono::Duration;
+
+    fn sub(self, other: JulianDay) -> chrono::Duration {
+        let days = self.day - other.day;
+        let fraction = self.fraction - other.fraction;
+        chrono::Duration::days(days as i64)
+            + chrono::Duration::nanoseconds((fraction * NANOSECONDS_PER_DAY).round() as i64)
+    }
+}
+
+/// Fails for Julian dates outside the range of `DateTime`, roughly 262,000 years either side of
+/// the common era.
+impl TryFrom<JulianDay> for DateTime<Utc> {
+    type Error = Error;
+
+    fn try_from(jd: JulianDay) -> Result<Self> {
+        sgp4_sys::split_julian_day_to_datetime(jd.day, jd.fraction).ok_or_else(|| {
+            Error::MalformedEpoch(format!(
+                "Julian date {} is outside the supported range",
+                jd.value()
+            ))
+        })
     }
 }
 
@@ -312,6 +935,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_state_vector_quantities() -> Result<()> {
+        use uom::si::{length::meter, velocity::meter_per_second};
+
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let tle = TwoLineElement::new(line1, line2)?;
+        let s1 = tle.propagate_to(tle.epoch()?)?;
+
+        let position = s1.position_quantities();
+        let velocity = s1.velocity_quantities();
+        for i in 0..3 {
+            assert!(approx_eq!(
+                f64,
+                position[i].get::<meter>(),
+                s1.position[i] * 1000.0
+            ));
+            assert!(approx_eq!(
+                f64,
+                velocity[i].get::<meter_per_second>(),
+                s1.velocity[i] * 1000.0
+            ));
+        }
+
+        let s2 = StateVector::from_quantities(s1.epoch, position, velocity);
+        assert!(vecs_eq(&s1.position, &s2.position));
+        assert!(vecs_eq(&s1.velocity, &s2.velocity));
+        assert!(approx_eq!(
+            f64,
+            s1.semimajor_axis().get::<kilometer>(),
+            s2.semimajor_axis().get::<kilometer>()
+        ));
+
+        // Quantities are kept through frame conversions.
+        let ecef = s1.to_ecef(&EarthOrientationParameters::default());
+        let radius = |p: [Length; 3]| (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+        assert!(approx_eq!(
+            f64,
+            radius(ecef.position_quantities()).get::<kilometer>(),
+            radius(position).get::<kilometer>(),
+            epsilon = 1e-9
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_decay_error() -> Result<()> {
         let line1 = "1 43051U 17071Q   22046.92182028  .07161566  12340-4  74927-3 0  9993";
@@ -328,6 +996,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_rejected_elements() {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 00.00000000228762";
+        assert_eq!(
+            TwoLineElement::new(line1, line2).unwrap_err(),
+            Error::MalformedTwoLineElement(TleParseError::RejectedElements(
+                sgp4_sys::Error::NegativeMeanMotion
+            ))
+        );
+    }
+
     #[test]
     fn mean_motion_round_trip() -> Result<()> {
         let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
@@ -336,6 +1016,227 @@ mod tests {
         let mean_motion = tle.mean_motion().get::<radian_per_second>() * 24. * 60.0 * 60.0
             / (2. * std::f64::consts::PI);
         assert!(approx_eq!(f64, mean_motion, 15.493968, epsilon = 0.01));
+
+        // The TLE value is the Kozai mean motion, which differs slightly from the one SGP4 uses.
+        let kozai = tle.kozai_mean_motion().get::<radian_per_second>() * 24. * 60.0 * 60.0
+            / (2. * std::f64::consts::PI);
+        assert!(approx_eq!(f64, kozai, 15.49396855, epsilon = 1e-9));
+        assert!(tle.mean_motion() != tle.kozai_mean_motion());
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_accessors() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let tle = TwoLineElement::new(line1, line2)?;
+
+        assert!(approx_eq!(
+            f64,
+            tle.inclination().get::<angle::degree>(),
+            51.6435
+        ));
+        assert!(approx_eq!(f64, tle.raan().get::<angle::degree>(), 92.2789));
+        assert!(approx_eq!(f64, tle.eccentricity(), 0.000257));
+        assert!(approx_eq!(
+            f64,
+            tle.argument_of_perigee().get::<angle::degree>(),
+            358.0648
+        ));
+        assert!(approx_eq!(
+            f64,
+            tle.mean_anomaly().get::<angle::degree>(),
+            144.9972
+        ));
+        assert!(approx_eq!(f64, tle.bstar(), 0.38778e-4));
+        assert!(approx_eq!(
+            f64,
+            tle.mean_motion_dot().get::<radian_per_second_squared>(),
+            2.0 * 0.00001715 * 2.0 * std::f64::consts::PI / 86400.0 / 86400.0
+        ));
+        assert_eq!(tle.mean_motion_ddot().get::<radian_per_second_cubed>(), 0.0);
+        assert_eq!(tle.classification(), 'U');
+        assert_eq!(tle.international_designator(), "98067A");
+        assert_eq!(tle.ephemeris_type(), 0);
+        assert_eq!(tle.element_set_number(), 999);
+        assert_eq!(tle.revolution_number(), 22876);
+        Ok(())
+    }
+
+    #[test]
+    fn test_setters_reinitialize_propagator() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let original = TwoLineElement::new(line1, line2)?;
+        let t = original.epoch()? + Duration::hours(2);
+        let s1 = original.propagate_to(t)?;
+
+        // Re-setting the current value should not change the propagated state.
+        let mut tle = original.clone();
+        tle.set_raan(tle.raan())?;
+        tle.set_kozai_mean_motion(tle.kozai_mean_motion())?;
+        let s2 = tle.propagate_to(t)?;
+        for i in 0..3 {
+            assert!(approx_eq!(
+                f64,
+                s1.position[i],
+                s2.position[i],
+                epsilon = 1e-6
+            ));
+        }
+
+        tle.set_raan(Angle::new::<angle::degree>(100.0))?;
+        assert!(approx_eq!(f64, tle.raan().get::<angle::degree>(), 100.0));
+        assert!(!vecs_eq(&s1.position, &tle.propagate_to(t)?.position));
+        assert!(tle.to_lines()?[1].starts_with("2 25544  51.6435 100.0000 "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejected_edit_leaves_tle_unchanged() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let mut tle = TwoLineElement::new(line1, line2)?;
+
+        assert!(tle.set_eccentricity(1.5).is_err());
+        assert!(approx_eq!(f64, tle.eccentricity(), 0.000257));
+        assert_eq!(tle.to_lines()?, [line1, line2]);
+        tle.propagate_to(tle.epoch()?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_modes() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let options = PropagationOptions::default();
+
+        let strict = TwoLineElement::parse(line1, line2, ParseMode::Lenient, options)?;
+        assert_eq!(strict.parse_mode(), Some(ParseMode::Strict));
+
+        let hand_typed = format!("{}\t", &line1[..68]);
+        assert!(TwoLineElement::parse(&hand_typed, line2, ParseMode::Strict, options).is_err());
+        let lenient = TwoLineElement::new(&hand_typed, line2)?;
+        assert_eq!(lenient.parse_mode(), Some(ParseMode::Lenient));
+        assert_eq!(lenient.to_lines()?, [line1, line2]);
+
+        // Edits rewrite the lines, which then pass the strict checks.
+        let mut edited = lenient.clone();
+        edited.set_bstar(0.5e-4)?;
+        assert_eq!(edited.parse_mode(), Some(ParseMode::Strict));
+        let mut edited = strict.clone();
+        edited.set_bstar(1e12)?;
+        assert_eq!(edited.parse_mode(), None);
+
+        let corrupted = line2.replace("51.6435", "51.6436");
+        assert!(TwoLineElement::new(line1, &corrupted).is_ok());
+        assert_eq!(
+            TwoLineElement::parse(line1, &corrupted, ParseMode::Strict, options).unwrap_err(),
+            Error::MalformedTwoLineElement(TleParseError::ChecksumMismatch {
+                line: 2,
+                expected: 8,
+                actual: 7
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_propagation_options() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let options = PropagationOptions {
+            gravity_model: GravityModel::Wgs72,
+            operation_mode: OperationMode::Afspc,
+        };
+        let wgs84 = TwoLineElement::new(line1, line2)?;
+        let wgs72 = TwoLineElement::new_with_options(line1, line2, options)?;
+        assert_eq!(wgs84.options(), PropagationOptions::default());
+        assert_eq!(wgs72.options(), options);
+
+        let t = wgs84.epoch()? + Duration::hours(6);
+        let s1 = wgs72.propagate_to(t)?;
+        assert!(!vecs_eq(&wgs84.propagate_to(t)?.position, &s1.position));
+
+        // Options are kept when the elements are edited.
+        let mut edited = wgs72.clone();
+        edited.set_bstar(edited.bstar())?;
+        assert_eq!(edited.options(), options);
+
+        let reinitialized = wgs84.with_options(options)?;
+        let s2 = reinitialized.propagate_to(t)?;
+        for i in 0..3 {
+            assert!(approx_eq!(
+                f64,
+                s1.position[i],
+                s2.position[i],
+                epsilon = 1e-6
+            ));
+        }
+
+        // The other constructors take the options directly.
+        let built = TwoLineElement::from_mean_elements_with_options(
+            wgs72.epoch()?,
+            wgs72.mean_elements(),
+            wgs72.bstar(),
+            wgs72.mean_motion_dot(),
+            wgs72.mean_motion_ddot(),
+            wgs72.catalog_number(),
+            options,
+        )?;
+        assert_eq!(built.options(), options);
+        let s3 = built.propagate_to(t)?;
+        for i in 0..3 {
+            assert!(approx_eq!(
+                f64,
+                s1.position[i],
+                s3.position[i],
+                epsilon = 1e-6
+            ));
+        }
+        let omm = TwoLineElement::from_omm_with_options(&wgs72.to_omm(), options)?;
+        assert_eq!(omm.options(), options);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_mean_elements() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let tle = TwoLineElement::new(line1, line2)?;
+
+        let built = TwoLineElement::from_mean_elements(
+            tle.epoch()?,
+            tle.mean_elements(),
+            tle.bstar(),
+            tle.mean_motion_dot(),
+            tle.mean_motion_ddot(),
+            tle.catalog_number(),
+        )?;
+        assert_eq!(built.catalog_number(), 25544);
+        assert!(approx_eq!(
+            f64,
+            built.mean_motion_dot().get::<radian_per_second_squared>(),
+            tle.mean_motion_dot().get::<radian_per_second_squared>()
+        ));
+
+        let t = tle.epoch()? + Duration::hours(6);
+        let s1 = tle.propagate_to(t)?;
+        let s2 = built.propagate_to(t)?;
+        for i in 0..3 {
+            assert!(approx_eq!(
+                f64,
+                s1.position[i],
+                s2.position[i],
+                epsilon = 1e-6
+            ));
+            assert!(approx_eq!(
+                f64,
+                s1.velocity[i],
+                s2.velocity[i],
+                epsilon = 1e-9
+            ));
+        }
         Ok(())
     }
 
@@ -386,20 +1287,151 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "xml")]
     #[test]
-    fn test_julian_day_identity() {
-        let t = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
-        assert_eq!(DateTime::<Utc>::from(JulianDay::from(t)), t);
+    fn test_omm_matches_tle() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let tle = TwoLineElement::new(line1, line2)?;
+
+        let xml = std::fs::read_to_string("test_data/omm.xml").unwrap();
+        let omm = TwoLineElement::from_omm(&OrbitMeanElements::parse_xml(&xml)?[0])?;
+
+        let t = tle.epoch()? + Duration::hours(6);
+        let s1 = tle.propagate_to(t)?;
+        let s2 = omm.propagate_to(t)?;
+        for i in 0..3 {
+            assert!(approx_eq!(
+                f64,
+                s1.position[i],
+                s2.position[i],
+                epsilon = 1e-6
+            ));
+            assert!(approx_eq!(
+                f64,
+                s1.velocity[i],
+                s2.velocity[i],
+                epsilon = 1e-9
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_alpha5_catalog_number() -> Result<()> {
+        let line1 = "1 A0001U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9993";
+        let line2 = "2 A0001  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228768";
+        let tle = TwoLineElement::new(line1, line2)?;
+        assert_eq!(tle.catalog_number(), 100_001);
+
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let iss = TwoLineElement::new(line1, line2)?;
+        assert_eq!(iss.catalog_number(), 25544);
+
+        let epoch = iss.epoch()?;
+        assert!(vecs_eq(
+            &tle.propagate_to(epoch)?.position,
+            &iss.propagate_to(epoch)?.position
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_lines_round_trip() -> Result<()> {
+        let line1 = "1 A0001U 98067A   20148.21301450  .00001715  00000+0  38778-4 0  9992";
+        let line2 = "2 A0001  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228768";
+        let tle = TwoLineElement::new(line1, line2)?;
+
+        assert_eq!(tle.to_lines()?, [line1, line2]);
+        Ok(())
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_omm_to_lines() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+
+        let xml = std::fs::read_to_string("test_data/omm.xml").unwrap();
+        let omm = TwoLineElement::from_omm(&OrbitMeanElements::parse_xml(&xml)?[0])?;
+        assert_eq!(omm.to_lines()?, [line1, line2]);
+        Ok(())
     }
 
     #[test]
-    fn test_gmst_conversion() {
+    fn test_to_omm() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let tle = TwoLineElement::new(line1, line2)?;
+
+        let omm = tle.to_omm();
+        assert_eq!(omm.object_id.as_deref(), Some("1998-067A"));
+        assert_eq!(omm.norad_cat_id, Some(25544));
+        assert_eq!(omm.epoch, tle.epoch()?);
+        assert_eq!(TwoLineElement::from_omm(&omm)?.to_lines()?, [line1, line2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_julian_day_identity() -> Result<()> {
         let t = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
-        let a: f64 = 100.1218209532; // GMST for 2020-01-01T00:00:00 in degrees
-        let a_rad = a.to_radians();
-        assert!(sgp4_sys::close(
-            GreenwichMeanSiderealTime::from(t).as_radians(),
-            a_rad
+        assert_eq!(DateTime::<Utc>::try_from(JulianDay::from(t))?, t);
+
+        let t = t.with_nanosecond(123_456_789).unwrap();
+        assert_eq!(DateTime::<Utc>::try_from(JulianDay::from(t))?, t);
+
+        assert!(matches!(
+            DateTime::<Utc>::try_from(JulianDay::new(1e12, 0.0)),
+            Err(Error::MalformedEpoch(_))
         ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_julian_day_arithmetic() -> Result<()> {
+        let t = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        let jd = JulianDay::from(t);
+        assert_eq!(jd.value(), 2451545.0);
+        assert_eq!(jd.parts(), (2451544.5, 0.5));
+        assert_eq!(jd.modified(), 51544.5);
+        assert_eq!(JulianDay::from_modified(51544.5), jd);
+        assert_eq!(JulianDay::new(2451545.0, 0.0), jd);
+
+        let later = jd + Duration::days(3) + Duration::nanoseconds(1_500);
+        assert_eq!(
+            DateTime::<Utc>::try_from(later)?,
+            t + Duration::days(3) + Duration::nanoseconds(1_500)
+        );
+        assert_eq!(later - jd, Duration::days(3) + Duration::nanoseconds(1_500));
+        assert_eq!(
+            later - Duration::hours(36) - jd,
+            Duration::hours(36) + Duration::nanoseconds(1_500)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_second_epoch() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let tle = TwoLineElement::new(line1, line2)?;
+
+        let epoch = Utc
+            .with_ymd_and_hms(2020, 5, 27, 5, 6, 44)
+            .unwrap()
+            .with_nanosecond(452_800_000)
+            .unwrap();
+        assert_eq!(tle.epoch()?, epoch);
+
+        // Half a millisecond moves the ISS by several meters.
+        let s1 = tle.propagate_to(epoch)?;
+        let s2 = tle.propagate_to(epoch + Duration::microseconds(500))?;
+        let moved = (0..3)
+            .map(|i| (s2.position[i] - s1.position[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        assert!(approx_eq!(f64, moved, 7.66e-3 * 0.5, epsilon = 1e-4));
+        Ok(())
     }
 }
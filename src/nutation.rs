@@ -0,0 +1,237 @@
+//! The IAU 1980 theory of nutation, and the IAU 1976 obliquity of the ecliptic.
+//!
+//! These are the models of the FK5 reduction, which TEME is defined against, and of the equation
+//! of the equinoxes that separates apparent from mean sidereal time.
+
+use std::f64::consts::TAU;
+
+use crate::JulianDay;
+
+/// The Julian date of J2000.0, 1 Jan 2000 12:00 TT.
+pub(crate) const J2000_JD: f64 = 2_451_545.0;
+
+const DAYS_PER_JULIAN_CENTURY: f64 = 36_525.0;
+
+pub(crate) const ARCSECONDS_TO_RADIANS: f64 = std::f64::consts::PI / (180.0 * 3600.0);
+
+/// Julian centuries of 36525 days since J2000.0, for a date in the scale of the model, normally TT.
+pub(crate) fn julian_centuries(jd: JulianDay) -> f64 {
+    let (day, fraction) = jd.parts();
+    ((day - J2000_JD) + fraction) / DAYS_PER_JULIAN_CENTURY
+}
+
+/// The mean obliquity of the ecliptic, in radians (IAU 1976).
+pub(crate) fn mean_obliquity(centuries: f64) -> f64 {
+    let t = centuries;
+    (84_381.448 + t * (-46.8150 + t * (-0.00059 + t * 0.001813))) * ARCSECONDS_TO_RADIANS
+}
+
+/// The Delaunay arguments of the IAU 1980 theory: the mean anomalies of the Moon and the Sun, the
+/// Moon's argument of latitude, the mean elongation of the Moon from the Sun, and the longitude of
+/// the Moon's ascending node, in radians.
+fn fundamental_arguments(centuries: f64) -> [f64; 5] {
+    let t = centuries;
+    let argument = |degrees: f64, arcseconds: [f64; 3]| {
+        let seconds = t * (arcseconds[0] + t * (arcseconds[1] + t * arcseconds[2]));
+        (degrees.to_radians() + seconds * ARCSECONDS_TO_RADIANS).rem_euclid(TAU)
+    };
+    [
+        argument(134.96298139, [1_717_915_922.633, 31.310, 0.064]),
+        argument(357.52772333, [129_596_581.224, -0.577, -0.012]),
+        argument(93.27191028, [1_739_527_263.137, -13.257, 0.011]),
+        argument(297.85036306, [1_602_961_601.328, -6.891, 0.019]),
+        argument(125.04452222, [-6_962_890.539, 7.455, 0.008]),
+    ]
+}
+
+/// Nutation in longitude and obliquity at an instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Nutation {
+    /// Nutation in longitude, Δψ, in radians.
+    pub longitude: f64,
+    /// Nutation in obliquity, Δε, in radians.
+    pub obliquity: f64,
+    /// The mean obliquity of the ecliptic, in radians.
+    pub mean_obliquity: f64,
+    /// The longitude of the Moon's ascending node, in radians.
+    moon_node: f64,
+}
+
+impl Nutation {
+    /// Evaluate the series at Julian centuries of TT since J2000.0.
+    pub(crate) fn at(centuries: f64) -> Self {
+        let t = centuries;
+        let arguments = fundamental_arguments(t);
+        let (mut longitude, mut obliquity) = (0.0, 0.0);
+        // Sum the smallest terms first.
+        for (multipliers, [a, b, c, d]) in TERMS.iter().rev() {
+            let angle: f64 = multipliers
+                .iter()
+                .zip(arguments.iter())
+                .map(|(&m, &argument)| m as f64 * argument)
+                .sum();
+            longitude += (a + b * t) * angle.sin();
+            obliquity += (c + d * t) * angle.cos();
+        }
+        Nutation {
+            longitude: longitude * 1e-4 * ARCSECONDS_TO_RADIANS,
+            obliquity: obliquity * 1e-4 * ARCSECONDS_TO_RADIANS,
+            mean_obliquity: mean_obliquity(t),
+            moon_node: arguments[4],
+        }
+    }
+
+    /// The equation of the equinoxes, in radians, including the terms adopted by the IAU in 1994.
+    ///
+    /// This is also the rotation between the TEME and true-of-date frames.
+    pub(crate) fn equation_of_equinoxes(&self) -> f64 {
+        self.longitude * self.mean_obliquity.cos()
+            + (0.00264 * self.moon_node.sin() + 0.000063 * (2.0 * self.moon_node).sin())
+                * ARCSECONDS_TO_RADIANS
+    }
+}
+
+/// The 106 terms of the IAU 1980 series: multipliers of the Delaunay arguments, then the
+/// coefficients of Δψ (constant and per century) and of Δε, in units of 0.0001″.
+#[rustfmt::skip]
+const TERMS: [([i8; 5], [f64; 4]); 106] = [
+    ([0, 0, 0, 0, 1], [-171996.0, -174.2, 92025.0, 8.9]),
+    ([0, 0, 2, -2, 2], [-13187.0, -1.6, 5736.0, -3.1]),
+    ([0, 0, 2, 0, 2], [-2274.0, -0.2, 977.0, -0.5]),
+    ([0, 0, 0, 0, 2], [2062.0, 0.2, -895.0, 0.5]),
+    ([0, 1, 0, 0, 0], [1426.0, -3.4, 54.0, -0.1]),
+    ([1, 0, 0, 0, 0], [712.0, 0.1, -7.0, 0.0]),
+    ([0, 1, 2, -2, 2], [-517.0, 1.2, 224.0, -0.6]),
+    ([0, 0, 2, 0, 1], [-386.0, -0.4, 200.0, 0.0]),
+    ([1, 0, 2, 0, 2], [-301.0, 0.0, 129.0, -0.1]),
+    ([0, -1, 2, -2, 2], [217.0, -0.5, -95.0, 0.3]),
+    ([1, 0, 0, -2, 0], [-158.0, 0.0, -1.0, 0.0]),
+    ([0, 0, 2, -2, 1], [129.0, 0.1, -70.0, 0.0]),
+    ([-1, 0, 2, 0, 2], [123.0, 0.0, -53.0, 0.0]),
+    ([1, 0, 0, 0, 1], [63.0, 0.1, -33.0, 0.0]),
+    ([0, 0, 0, 2, 0], [63.0, 0.0, -2.0, 0.0]),
+    ([-1, 0, 2, 2, 2], [-59.0, 0.0, 26.0, 0.0]),
+    ([-1, 0, 0, 0, 1], [-58.0, -0.1, 32.0, 0.0]),
+    ([1, 0, 2, 0, 1], [-51.0, 0.0, 27.0, 0.0]),
+    ([2, 0, 0, -2, 0], [48.0, 0.0, 1.0, 0.0]),
+    ([-2, 0, 2, 0, 1], [46.0, 0.0, -24.0, 0.0]),
+    ([0, 0, 2, 2, 2], [-38.0, 0.0, 16.0, 0.0]),
+    ([2, 0, 2, 0, 2], [-31.0, 0.0, 13.0, 0.0]),
+    ([2, 0, 0, 0, 0], [29.0, 0.0, -1.0, 0.0]),
+    ([1, 0, 2, -2, 2], [29.0, 0.0, -12.0, 0.0]),
+    ([0, 0, 2, 0, 0], [26.0, 0.0, -1.0, 0.0]),
+    ([0, 0, 2, -2, 0], [-22.0, 0.0, 0.0, 0.0]),
+    ([-1, 0, 2, 0, 1], [21.0, 0.0, -10.0, 0.0]),
+    ([0, 2, 0, 0, 0], [17.0, -0.1, 0.0, 0.0]),
+    ([0, 2, 2, -2, 2], [-16.0, 0.1, 7.0, 0.0]),
+    ([-1, 0, 0, 2, 1], [16.0, 0.0, -8.0, 0.0]),
+    ([0, 1, 0, 0, 1], [-15.0, 0.0, 9.0, 0.0]),
+    ([1, 0, 0, -2, 1], [-13.0, 0.0, 7.0, 0.0]),
+    ([0, -1, 0, 0, 1], [-12.0, 0.0, 6.0, 0.0]),
+    ([2, 0, -2, 0, 0], [11.0, 0.0, 0.0, 0.0]),
+    ([-1, 0, 2, 2, 1], [-10.0, 0.0, 5.0, 0.0]),
+    ([1, 0, 2, 2, 2], [-8.0, 0.0, 3.0, 0.0]),
+    ([0, -1, 2, 0, 2], [-7.0, 0.0, 3.0, 0.0]),
+    ([0, 0, 2, 2, 1], [-7.0, 0.0, 3.0, 0.0]),
+    ([1, 1, 0, -2, 0], [-7.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 2, 0, 2], [7.0, 0.0, -3.0, 0.0]),
+    ([-2, 0, 0, 2, 1], [-6.0, 0.0, 3.0, 0.0]),
+    ([0, 0, 0, 2, 1], [-6.0, 0.0, 3.0, 0.0]),
+    ([2, 0, 2, -2, 2], [6.0, 0.0, -3.0, 0.0]),
+    ([1, 0, 0, 2, 0], [6.0, 0.0, 0.0, 0.0]),
+    ([1, 0, 2, -2, 1], [6.0, 0.0, -3.0, 0.0]),
+    ([0, 0, 0, -2, 1], [-5.0, 0.0, 3.0, 0.0]),
+    ([0, -1, 2, -2, 1], [-5.0, 0.0, 3.0, 0.0]),
+    ([2, 0, 2, 0, 1], [-5.0, 0.0, 3.0, 0.0]),
+    ([1, -1, 0, 0, 0], [5.0, 0.0, 0.0, 0.0]),
+    ([1, 0, 0, -1, 0], [-4.0, 0.0, 0.0, 0.0]),
+    ([0, 0, 0, 1, 0], [-4.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 0, -2, 0], [-4.0, 0.0, 0.0, 0.0]),
+    ([1, 0, -2, 0, 0], [4.0, 0.0, 0.0, 0.0]),
+    ([2, 0, 0, -2, 1], [4.0, 0.0, -2.0, 0.0]),
+    ([0, 1, 2, -2, 1], [4.0, 0.0, -2.0, 0.0]),
+    ([1, 1, 0, 0, 0], [-3.0, 0.0, 0.0, 0.0]),
+    ([1, -1, 0, -1, 0], [-3.0, 0.0, 0.0, 0.0]),
+    ([-1, -1, 2, 2, 2], [-3.0, 0.0, 1.0, 0.0]),
+    ([0, -1, 2, 2, 2], [-3.0, 0.0, 1.0, 0.0]),
+    ([1, -1, 2, 0, 2], [-3.0, 0.0, 1.0, 0.0]),
+    ([3, 0, 2, 0, 2], [-3.0, 0.0, 1.0, 0.0]),
+    ([-2, 0, 2, 0, 2], [-3.0, 0.0, 1.0, 0.0]),
+    ([1, 0, 2, 0, 0], [3.0, 0.0, 0.0, 0.0]),
+    ([-1, 0, 2, 4, 2], [-2.0, 0.0, 1.0, 0.0]),
+    ([1, 0, 0, 0, 2], [-2.0, 0.0, 1.0, 0.0]),
+    ([-1, 0, 2, -2, 1], [-2.0, 0.0, 1.0, 0.0]),
+    ([0, -2, 2, -2, 1], [-2.0, 0.0, 1.0, 0.0]),
+    ([-2, 0, 0, 0, 1], [-2.0, 0.0, 1.0, 0.0]),
+    ([2, 0, 0, 0, 1], [2.0, 0.0, -1.0, 0.0]),
+    ([3, 0, 0, 0, 0], [2.0, 0.0, 0.0, 0.0]),
+    ([1, 1, 2, 0, 2], [2.0, 0.0, -1.0, 0.0]),
+    ([0, 0, 2, 1, 2], [2.0, 0.0, -1.0, 0.0]),
+    ([1, 0, 0, 2, 1], [-1.0, 0.0, 0.0, 0.0]),
+    ([1, 0, 2, 2, 1], [-1.0, 0.0, 1.0, 0.0]),
+    ([1, 1, 0, -2, 1], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 0, 2, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 2, -2, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 1, -2, 2, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([1, 0, -2, 2, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([1, 0, -2, -2, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([1, 0, 2, -2, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([1, 0, 0, -4, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([2, 0, 0, -4, 0], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 0, 2, 4, 2], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 0, 2, -1, 2], [-1.0, 0.0, 0.0, 0.0]),
+    ([-2, 0, 2, 4, 2], [-1.0, 0.0, 1.0, 0.0]),
+    ([2, 0, 2, 2, 2], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, -1, 2, 0, 1], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 0, -2, 0, 1], [-1.0, 0.0, 0.0, 0.0]),
+    ([0, 0, 4, -2, 2], [1.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 0, 0, 2], [1.0, 0.0, 0.0, 0.0]),
+    ([1, 1, 2, -2, 2], [1.0, 0.0, -1.0, 0.0]),
+    ([3, 0, 2, -2, 2], [1.0, 0.0, 0.0, 0.0]),
+    ([-2, 0, 2, 2, 2], [1.0, 0.0, -1.0, 0.0]),
+    ([-1, 0, 0, 0, 2], [1.0, 0.0, -1.0, 0.0]),
+    ([0, 0, -2, 2, 1], [1.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 2, 0, 1], [1.0, 0.0, 0.0, 0.0]),
+    ([-1, 0, 4, 0, 2], [1.0, 0.0, 0.0, 0.0]),
+    ([2, 1, 0, -2, 0], [1.0, 0.0, 0.0, 0.0]),
+    ([2, 0, 0, 2, 0], [1.0, 0.0, 0.0, 0.0]),
+    ([2, 0, 2, -2, 1], [1.0, 0.0, -1.0, 0.0]),
+    ([2, 0, -2, 0, 1], [1.0, 0.0, 0.0, 0.0]),
+    ([1, -1, 0, -2, 0], [1.0, 0.0, 0.0, 0.0]),
+    ([-1, 0, 0, 1, 1], [1.0, 0.0, 0.0, 0.0]),
+    ([-1, -1, 0, 2, 1], [1.0, 0.0, 0.0, 0.0]),
+    ([0, 1, 0, 1, 0], [1.0, 0.0, 0.0, 0.0]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_nutation() {
+        // Reference values from the IAU SOFA routines iauNut80 and iauEqeq94.
+        let nutation = Nutation::at(julian_centuries(JulianDay::new(2_400_000.5, 53_736.0)));
+        assert!(approx_eq!(
+            f64,
+            nutation.longitude,
+            -0.964365835322656e-5,
+            epsilon = 1e-11
+        ));
+        assert!(approx_eq!(
+            f64,
+            nutation.obliquity,
+            0.406005100687971e-4,
+            epsilon = 1e-11
+        ));
+
+        let nutation = Nutation::at(julian_centuries(JulianDay::new(2_400_000.5, 41_234.0)));
+        assert!(approx_eq!(
+            f64,
+            nutation.equation_of_equinoxes(),
+            0.535775825460926e-4,
+            epsilon = 1e-11
+        ));
+    }
+}
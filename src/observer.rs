@@ -0,0 +1,192 @@
+//! The view of a satellite from an observer on the ground.
+
+use std::f64::consts::TAU;
+
+use uom::si::{
+    angle::radian,
+    f64::{Angle, Length, Velocity},
+    length::kilometer,
+    velocity::kilometer_per_second,
+};
+
+use crate::{EarthOrientationParameters, Ellipsoid, GeodeticPosition, StateVector, Topocentric};
+
+/// An observer fixed to the Earth, such as a ground station.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observer {
+    pub position: GeodeticPosition,
+
+    /// The ellipsoid to which the position is referred, WGS84 by default.
+    pub ellipsoid: Ellipsoid,
+}
+
+impl Observer {
+    /// Create an observer at a geodetic latitude, longitude and altitude on the WGS84 ellipsoid.
+    pub fn new(latitude: Angle, longitude: Angle, altitude: Length) -> Self {
+        Observer::from_geodetic(GeodeticPosition::new(latitude, longitude, altitude))
+    }
+
+    /// Create an observer at a position on the WGS84 ellipsoid.
+    pub fn from_geodetic(position: GeodeticPosition) -> Self {
+        Observer {
+            position,
+            ellipsoid: Ellipsoid::wgs84(),
+        }
+    }
+
+    /// Use a different reference ellipsoid for the observer's position.
+    pub fn with_ellipsoid(self, ellipsoid: Ellipsoid) -> Self {
+        Observer { ellipsoid, ..self }
+    }
+
+    /// The direction and distance of a satellite from the observer.
+    ///
+    /// The TEME state is rotated to the Earth-fixed frame by Greenwich mean sidereal time, taking
+    /// UT1 as UTC and ignoring polar motion. This can misplace a satellite in low Earth orbit by up
+    /// to half a kilometre; use [Observer::look_angles_with] to supply Earth orientation parameters.
+    pub fn look_angles(&self, state: &StateVector) -> LookAngles {
+        self.look_angles_with(state, &EarthOrientationParameters::default())
+    }
+
+    /// The direction and distance of a satellite from the observer, with the given Earth
+    /// orientation parameters.
+    pub fn look_angles_with(
+        &self,
+        state: &StateVector,
+        eop: &EarthOrientationParameters,
+    ) -> LookAngles {
+        let topocentric = self.topocentric(state, eop);
+        let [east, north, up] = *topocentric.position;
+        let range = topocentric.position.norm();
+
+        LookAngles {
+            azimuth: Angle::new::<radian>(east.atan2(north).rem_euclid(TAU)),
+            elevation: Angle::new::<radian>((up / range).asin()),
+            range: Length::new::<kilometer>(range),
+            range_rate: Velocity::new::<kilometer_per_second>(
+                topocentric.position.dot(&topocentric.velocity) / range,
+            ),
+        }
+    }
+
+    /// The state of a satellite relative to the observer, in its east, north, up frame.
+    pub(crate) fn topocentric(
+        &self,
+        state: &StateVector,
+        eop: &EarthOrientationParameters,
+    ) -> StateVector<Topocentric> {
+        state
+            .to_ecef(eop)
+            .to_topocentric(&self.position, &self.ellipsoid)
+    }
+}
+
+/// The direction of a satellite in an observer's sky, and its distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LookAngles {
+    /// The azimuth, measured clockwise from north, from 0 to 2π.
+    pub azimuth: Angle,
+
+    /// The elevation above the observer's horizon, perpendicular to the ellipsoid.
+    pub elevation: Angle,
+
+    /// The slant range from the observer to the satellite.
+    pub range: Length,
+
+    /// The rate of change of the range, positive when the satellite is moving away.
+    pub range_rate: Velocity,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{TimeZone, Utc};
+    use float_cmp::approx_eq;
+    use uom::si::angle::degree;
+
+    use crate::{Itrf, Result, TwoLineElement};
+
+    #[test]
+    fn test_look_angles() {
+        let epoch = Utc.with_ymd_and_hms(2020, 5, 27, 12, 0, 0).unwrap();
+        let eop = EarthOrientationParameters::default();
+        let observer = Observer::new(
+            Angle::new::<degree>(0.0),
+            Angle::new::<degree>(0.0),
+            Length::new::<kilometer>(0.0),
+        );
+
+        // To the north east on the horizon, moving away along the line of sight and up.
+        let state = StateVector::<Itrf>::from_vectors(
+            epoch,
+            [6378.137, 1000.0, 1000.0].into(),
+            [1.0, 3.0, 4.0].into(),
+        )
+        .to_teme(&eop);
+        let look = observer.look_angles(&state);
+        assert!(approx_eq!(
+            f64,
+            look.azimuth.get::<degree>(),
+            45.0,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            look.elevation.get::<degree>(),
+            0.0,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            look.range.get::<kilometer>(),
+            1000.0 * 2f64.sqrt(),
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            look.range_rate.get::<kilometer_per_second>(),
+            7.0 / 2f64.sqrt(),
+            epsilon = 1e-9
+        ));
+    }
+
+    #[test]
+    fn test_overhead() -> Result<()> {
+        let tle = TwoLineElement::new(
+            "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992",
+            "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767",
+        )?;
+        let state = tle.propagate_to(tle.epoch()?)?;
+
+        // An observer directly beneath the satellite sees it at the zenith, at its altitude.
+        let point = state.geodetic();
+        let observer = Observer::new(
+            point.latitude,
+            point.longitude,
+            Length::new::<kilometer>(0.0),
+        );
+        let look = observer.look_angles(&state);
+        assert!(approx_eq!(
+            f64,
+            look.elevation.get::<degree>(),
+            90.0,
+            epsilon = 1e-6
+        ));
+        assert!(approx_eq!(
+            f64,
+            look.range.get::<kilometer>(),
+            point.height.get::<kilometer>(),
+            epsilon = 1e-6
+        ));
+
+        // The satellite is far below the horizon from the other side of the Earth.
+        let antipode = Observer::new(
+            -point.latitude,
+            point.longitude - Angle::new::<degree>(180.0),
+            Length::new::<kilometer>(0.0),
+        );
+        assert!(antipode.look_angles(&state).elevation.get::<degree>() < -80.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+//! CCSDS Orbit Mean-Elements Message (OMM) support.
+//!
+//! An OMM carries the same SGP4 mean elements as a TLE, but as named keywords rather than fixed
+//! columns, so it can hold 9-digit catalog numbers and full-precision epochs. See CCSDS 502.0-B-3
+//! for the message definition.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use uom::si::{
+    angle::degree,
+    angular_velocity::revolution_per_hour,
+    f64::{Angle, AngularVelocity},
+};
+
+use crate::{Error, Result};
+
+/// The mean elements and metadata of a single Orbit Mean-Elements Message.
+///
+/// Only messages using the SGP4 mean element theory in the UTC time system can be represented, as
+/// these are the only ones which may be propagated with this crate. Use
+/// [TwoLineElement::from_omm](crate::TwoLineElement::from_omm) to obtain a propagator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrbitMeanElements {
+    pub object_name: Option<String>,
+    /// The international designator of the object, e.g. `1998-067A`.
+    pub object_id: Option<String>,
+    pub epoch: DateTime<Utc>,
+    pub mean_motion: AngularVelocity,
+    pub eccentricity: f64,
+    pub inclination: Angle,
+    pub raan: Angle,
+    pub argument_of_pericenter: Angle,
+    pub mean_anomaly: Angle,
+    pub ephemeris_type: u8,
+    pub classification_type: char,
+    pub norad_cat_id: Option<u32>,
+    pub element_set_no: Option<u32>,
+    pub rev_at_epoch: Option<u32>,
+    /// The SGP4 drag term, in inverse Earth radii.
+    pub bstar: f64,
+    /// The first derivative of mean motion, in revolutions per day squared, as given in a TLE.
+    pub mean_motion_dot: f64,
+    /// The second derivative of mean motion, in revolutions per day cubed, as given in a TLE.
+    pub mean_motion_ddot: f64,
+}
+
+impl OrbitMeanElements {
+    /// Parse every message in an OMM XML document.
+    ///
+    /// This accepts either a bare `<omm>` document, or an `<ndm>` combined instantiation document
+    /// containing several messages, as published by CelesTrak and Space-Track.
+    #[cfg(feature = "xml")]
+    pub fn parse_xml(xml: &str) -> Result<Vec<OrbitMeanElements>> {
+        use quick_xml::events::Event;
+        use quick_xml::Reader;
+        use std::collections::HashMap;
+
+        let xml_error = |e: quick_xml::Error| Error::MalformedOrbitMeanElements(e.to_string());
+
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+
+        let mut messages = vec![];
+        let mut keywords: Option<HashMap<String, String>> = None;
+        let mut current_tag: Option<String> = None;
+
+        loop {
+            match reader.read_event().map_err(xml_error)? {
+                Event::Start(tag) => {
+                    let name = String::from_utf8_lossy(tag.local_name().as_ref()).into_owned();
+                    if name == "omm" {
+                        keywords = Some(HashMap::new());
+                    }
+                    current_tag = Some(name);
+                }
+                Event::Text(text) => {
+                    if let (Some(keywords), Some(tag)) = (keywords.as_mut(), current_tag.as_ref()) {
+                        let value = text.unescape().map_err(xml_error)?;
+                        keywords.insert(tag.clone(), value.into_owned());
+                    }
+                }
+                Event::End(tag) => {
+                    if tag.local_name().as_ref() == b"omm" {
+                        if let Some(keywords) = keywords.take() {
+                            messages.push(Self::from_keywords(|k| {
+                                keywords.get(k).map(String::as_str)
+                            })?);
+                        }
+                    }
+                    current_tag = None;
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Parse the general perturbations JSON format served by CelesTrak and Space-Track.
+    ///
+    /// The document may be a single object or an array of objects keyed by OMM keyword. Values may
+    /// be given either as JSON numbers (CelesTrak) or as strings (Space-Track).
+    #[cfg(feature = "json")]
+    pub fn parse_json(json: &str) -> Result<Vec<OrbitMeanElements>> {
+        use serde_json::Value;
+        use std::collections::HashMap;
+
+        let json_error = |e: serde_json::Error| Error::MalformedOrbitMeanElements(e.to_string());
+        let objects = match serde_json::from_str(json).map_err(json_error)? {
+            Value::Array(objects) => objects,
+            object => vec![object],
+        };
+
+        objects
+            .iter()
+            .map(|object| {
+                let object = object.as_object().ok_or_else(|| {
+                    Error::MalformedOrbitMeanElements(format!("Expected an object, got {}", object))
+                })?;
+                let keywords: HashMap<&str, String> = object
+                    .iter()
+                    .filter_map(|(key, value)| match value {
+                        Value::String(s) => Some((key.as_str(), s.clone())),
+                        Value::Number(n) => Some((key.as_str(), n.to_string())),
+                        _ => None,
+                    })
+                    .collect();
+                Self::from_keywords(|k| keywords.get(k).map(String::as_str))
+            })
+            .collect()
+    }
+
+    /// Parse the general perturbations CSV format served by CelesTrak and Space-Track.
+    ///
+    /// The first row must be a header naming the OMM keyword of each column.
+    #[cfg(feature = "csv")]
+    pub fn parse_csv(csv: &str) -> Result<Vec<OrbitMeanElements>> {
+        let csv_error = |e: csv::Error| Error::MalformedOrbitMeanElements(e.to_string());
+
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(csv.as_bytes());
+        let headers = reader.headers().map_err(csv_error)?.clone();
+
+        reader
+            .records()
+            .map(|record| {
+                let record = record.map_err(csv_error)?;
+                Self::from_keywords(|k| {
+                    headers
+                        .iter()
+                        .position(|h| h == k)
+                        .and_then(|i| record.get(i))
+                })
+            })
+            .collect()
+    }
+
+    /// Build an element set from OMM keyword/value pairs, looked up by their CCSDS keyword name.
+    ///
+    /// This is the common path for every OMM encoding, and can be used directly for encodings
+    /// which this crate does not parse itself.
+    pub fn from_keywords<'a>(get: impl Fn(&str) -> Option<&'a str>) -> Result<Self> {
+        let get = |key: &str| get(key).map(str::trim).filter(|v| !v.is_empty());
+        let required = |key: &str| {
+            get(key).ok_or_else(|| {
+                Error::MalformedOrbitMeanElements(format!("Missing required keyword {}", key))
+            })
+        };
+        let number = |key: &str, value: &str| {
+            value.parse::<f64>().map_err(|_| {
+                Error::MalformedOrbitMeanElements(format!("{} is not a number: {}", key, value))
+            })
+        };
+        let required_number = |key: &str| required(key).and_then(|v| number(key, v));
+        let optional_number = |key: &str| get(key).map_or(Ok(0.0), |v| number(key, v));
+        let optional_integer = |key: &str| {
+            get(key)
+                .map(|v| {
+                    v.parse::<u32>().map_err(|_| {
+                        Error::MalformedOrbitMeanElements(format!(
+                            "{} is not an integer: {}",
+                            key, v
+                        ))
+                    })
+                })
+                .transpose()
+        };
+
+        if let Some(time_system) = get("TIME_SYSTEM") {
+            if time_system != "UTC" {
+                return Err(Error::MalformedOrbitMeanElements(format!(
+                    "Unsupported time system {}",
+                    time_system
+                )));
+            }
+        }
+
+        if let Some(theory) = get("MEAN_ELEMENT_THEORY") {
+            if !matches!(theory, "SGP4" | "SGP/SGP4") {
+                return Err(Error::MalformedOrbitMeanElements(format!(
+                    "Unsupported mean element theory {}",
+                    theory
+                )));
+            }
+        }
+
+        let ephemeris_type = optional_integer("EPHEMERIS_TYPE")?.unwrap_or(0);
+        let classification_type = get("CLASSIFICATION_TYPE")
+            .and_then(|c| c.chars().next())
+            .unwrap_or('U');
+
+        Ok(OrbitMeanElements {
+            object_name: get("OBJECT_NAME").map(str::to_owned),
+            object_id: get("OBJECT_ID").map(str::to_owned),
+            epoch: parse_epoch(required("EPOCH")?)?,
+            mean_motion: AngularVelocity::new::<revolution_per_hour>(
+                required_number("MEAN_MOTION")? / 24.0,
+            ),
+            eccentricity: required_number("ECCENTRICITY")?,
+            inclination: Angle::new::<degree>(required_number("INCLINATION")?),
+            raan: Angle::new::<degree>(required_number("RA_OF_ASC_NODE")?),
+            argument_of_pericenter: Angle::new::<degree>(required_number("ARG_OF_PERICENTER")?),
+            mean_anomaly: Angle::new::<degree>(required_number("MEAN_ANOMALY")?),
+            ephemeris_type: u8::try_from(ephemeris_type).map_err(|_| {
+                Error::MalformedOrbitMeanElements(format!(
+                    "EPHEMERIS_TYPE is out of range: {}",
+                    ephemeris_type
+                ))
+            })?,
+            classification_type,
+            norad_cat_id: optional_integer("NORAD_CAT_ID")?,
+            element_set_no: optional_integer("ELEMENT_SET_NO")?,
+            rev_at_epoch: optional_integer("REV_AT_EPOCH")?,
+            bstar: optional_number("BSTAR")?,
+            mean_motion_dot: optional_number("MEAN_MOTION_DOT")?,
+            mean_motion_ddot: optional_number("MEAN_MOTION_DDOT")?,
+        })
+    }
+}
+
+/// Parse a CCSDS epoch, in either calendar or day-of-year form.
+fn parse_epoch(epoch: &str) -> Result<DateTime<Utc>> {
+    let epoch = epoch.trim_end_matches('Z');
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%jT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(epoch, format).ok())
+        .map(|t| t.and_utc())
+        .ok_or_else(|| Error::MalformedOrbitMeanElements(format!("Invalid epoch {}", epoch)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn test_epoch_formats() -> Result<()> {
+        let expected = Utc
+            .with_ymd_and_hms(2020, 5, 27, 5, 6, 44)
+            .unwrap()
+            .with_nanosecond(452_800_000)
+            .unwrap();
+        assert_eq!(parse_epoch("2020-05-27T05:06:44.452800")?, expected);
+        assert_eq!(parse_epoch("2020-05-27T05:06:44.4528Z")?, expected);
+        assert_eq!(parse_epoch("2020-148T05:06:44.4528")?, expected);
+        assert!(parse_epoch("20148.21301450").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_keyword() {
+        let keywords: HashMap<&str, &str> = [("EPOCH", "2020-05-27T05:06:44.4528")].into();
+        let result = OrbitMeanElements::from_keywords(|k| keywords.get(k).copied());
+        assert_eq!(
+            result,
+            Err(Error::MalformedOrbitMeanElements(
+                "Missing required keyword MEAN_MOTION".to_owned()
+            ))
+        );
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_parse_xml() -> Result<()> {
+        use float_cmp::approx_eq;
+
+        let xml = std::fs::read_to_string("test_data/omm.xml").unwrap();
+        let messages = OrbitMeanElements::parse_xml(&xml)?;
+
+        assert_eq!(messages.len(), 2);
+        let iss = &messages[0];
+        assert_eq!(iss.object_name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(iss.object_id.as_deref(), Some("1998-067A"));
+        assert_eq!(iss.norad_cat_id, Some(25544));
+        assert_eq!(iss.epoch.nanosecond(), 452_800_000);
+        assert!(approx_eq!(f64, iss.inclination.get::<degree>(), 51.6435));
+        assert!(approx_eq!(f64, iss.bstar, 0.38778e-4));
+        assert_eq!(messages[1].object_name.as_deref(), Some("LEMUR-1"));
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_parse_json() -> Result<()> {
+        let json = std::fs::read_to_string("test_data/gp.json").unwrap();
+        let messages = OrbitMeanElements::parse_json(&json)?;
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].object_name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(messages[0].epoch.nanosecond(), 452_800_000);
+        assert_eq!(messages[1].norad_cat_id, Some(40044));
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_parse_json_string_values() -> Result<()> {
+        let json = r#"{
+            "OBJECT_NAME": "TEST OBJECT", "OBJECT_ID": "2024-001A",
+            "EPOCH": "2024-01-01T12:00:00.123456", "MEAN_MOTION": "15.0",
+            "ECCENTRICITY": "0.0001", "INCLINATION": "53.0", "RA_OF_ASC_NODE": "10.0",
+            "ARG_OF_PERICENTER": "90.0", "MEAN_ANOMALY": "270.0", "NORAD_CAT_ID": "270000001",
+            "BSTAR": "0.0001", "MEAN_MOTION_DOT": "0.00001", "MEAN_MOTION_DDOT": "0"
+        }"#;
+        let messages = OrbitMeanElements::parse_json(json)?;
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].norad_cat_id, Some(270_000_001));
+        assert_eq!(messages[0].epoch.nanosecond(), 123_456_000);
+        Ok(())
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_parse_csv() -> Result<()> {
+        let csv = std::fs::read_to_string("test_data/gp.csv").unwrap();
+        let messages = OrbitMeanElements::parse_csv(&csv)?;
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].object_id.as_deref(), Some("1998-067A"));
+        assert_eq!(messages[1].rev_at_epoch, Some(31974));
+        Ok(())
+    }
+
+    #[cfg(all(feature = "xml", feature = "json", feature = "csv"))]
+    #[test]
+    fn test_formats_agree() -> Result<()> {
+        let xml =
+            OrbitMeanElements::parse_xml(&std::fs::read_to_string("test_data/omm.xml").unwrap())?;
+        let json =
+            OrbitMeanElements::parse_json(&std::fs::read_to_string("test_data/gp.json").unwrap())?;
+        let csv =
+            OrbitMeanElements::parse_csv(&std::fs::read_to_string("test_data/gp.csv").unwrap())?;
+
+        assert_eq!(xml, json);
+        assert_eq!(xml, csv);
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+//! Prediction of the passes of a satellite over an observer.
+//!
+//! The elevation of the satellite is sampled at a hundredth of its orbital period, which is short
+//! enough that between two samples it has at most one maximum or minimum. Each extremum is found by
+//! bisection on the elevation rate, and splits the interval into parts in which the elevation is
+//! monotonic; crossings of the minimum elevation are then found by bisection on the elevation.
+
+use std::f64::consts::TAU;
+
+use chrono::{DateTime, Duration, Utc};
+use uom::si::{angle::radian, angular_velocity::radian_per_second, f64::Angle};
+
+use crate::{EarthOrientationParameters, Observer, Result, TwoLineElement};
+
+/// The number of elevation samples taken per orbital period.
+const SAMPLES_PER_PERIOD: f64 = 100.0;
+
+/// The precision to which the times of events are found.
+const TIME_TOLERANCE: Duration = Duration::milliseconds(1);
+
+/// A pass of a satellite above an observer's minimum elevation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pass {
+    /// Acquisition of signal, when the satellite rises above the minimum elevation.
+    pub aos: DateTime<Utc>,
+
+    /// Culmination, when the satellite reaches its greatest elevation. This is commonly called the
+    /// time of closest approach, though the range is least at a slightly different time.
+    pub tca: DateTime<Utc>,
+
+    /// Loss of signal, when the satellite sets below the minimum elevation.
+    pub los: DateTime<Utc>,
+
+    /// The elevation at culmination.
+    pub max_elevation: Angle,
+
+    /// The azimuth at acquisition of signal.
+    pub aos_azimuth: Angle,
+
+    /// The azimuth at loss of signal.
+    pub los_azimuth: Angle,
+}
+
+impl Pass {
+    /// The time from acquisition to loss of signal.
+    pub fn duration(&self) -> Duration {
+        self.los - self.aos
+    }
+
+    /// The pass between two crossings of the minimum elevation, culminating at the highest of the
+    /// maxima between them.
+    fn between(aos: Sample, los: Sample, maxima: &[Sample]) -> Self {
+        let culmination = maxima
+            .iter()
+            .filter(|s| aos.time <= s.time && s.time <= los.time)
+            .chain([&aos, &los])
+            .fold(aos, |best, &s| {
+                if s.elevation > best.elevation {
+                    s
+                } else {
+                    best
+                }
+            });
+        Pass {
+            aos: aos.time,
+            tca: culmination.time,
+            los: los.time,
+            max_elevation: Angle::new::<radian>(culmination.elevation),
+            aos_azimuth: Angle::new::<radian>(aos.azimuth),
+            los_azimuth: Angle::new::<radian>(los.azimuth),
+        }
+    }
+}
+
+impl Observer {
+    /// Find every pass of a satellite above `min_elevation` between `start` and `end`.
+    ///
+    /// The acquisition, culmination and loss of signal times are found to within a millisecond. A
+    /// pass which is already in progress at `start`, or still in progress at `end`, is cut short
+    /// there, so its acquisition or loss of signal is at the edge of the window. As with
+    /// [Observer::look_angles], UT1 is taken as UTC and polar motion is ignored; use
+    /// [Observer::passes_with] to supply Earth orientation parameters.
+    pub fn passes(
+        &self,
+        tle: &TwoLineElement,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_elevation: Angle,
+    ) -> Result<Vec<Pass>> {
+        self.passes_with(
+            tle,
+            start,
+            end,
+            min_elevation,
+            &EarthOrientationParameters::default(),
+        )
+    }
+
+    /// Find every pass of a satellite above `min_elevation` between `start` and `end`, with the
+    /// given Earth orientation parameters.
+    pub fn passes_with(
+        &self,
+        tle: &TwoLineElement,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        min_elevation: Angle,
+        eop: &EarthOrientationParameters,
+    ) -> Result<Vec<Pass>> {
+        if end <= start {
+            return Ok(Vec::new());
+        }
+
+        let sky = Sky {
+            observer: self,
+            tle,
+            eop,
+            min_elevation: min_elevation.get::<radian>(),
+        };
+        let period = TAU / tle.mean_motion().get::<radian_per_second>();
+        let step = Duration::nanoseconds((period / SAMPLES_PER_PERIOD * 1e9) as i64);
+
+        let mut time = start;
+        let mut samples = vec![sky.sample(start)?];
+        while time < end {
+            time = std::cmp::min(time + step, end);
+            samples.push(sky.sample(time)?);
+        }
+
+        let mut maxima = Vec::new();
+        let mut crossings = Vec::new();
+        for pair in samples.windows(2) {
+            let (before, after) = (pair[0], pair[1]);
+            let mut monotonic = vec![(before, after)];
+            if (before.elevation_rate > 0.0) != (after.elevation_rate > 0.0) {
+                let extremum = sky.bisect(before, after, |s| s.elevation_rate)?;
+                if before.elevation_rate > 0.0 {
+                    maxima.push(extremum);
+                }
+                monotonic = vec![(before, extremum), (extremum, after)];
+            }
+            for (before, after) in monotonic {
+                if before.is_visible(&sky) != after.is_visible(&sky) {
+                    crossings.push(sky.bisect(before, after, |s| s.elevation - sky.min_elevation)?);
+                }
+            }
+        }
+
+        let mut passes = Vec::new();
+        let mut acquisition = Some(samples[0]).filter(|s| s.is_visible(&sky));
+        for crossing in crossings {
+            match acquisition.take() {
+                None => acquisition = Some(crossing),
+                Some(aos) => passes.push(Pass::between(aos, crossing, &maxima)),
+            }
+        }
+        if let Some(aos) = acquisition {
+            passes.push(Pass::between(aos, *samples.last().unwrap(), &maxima));
+        }
+        Ok(passes)
+    }
+}
+
+/// The satellite as seen by an observer.
+struct Sky<'a> {
+    observer: &'a Observer,
+    tle: &'a TwoLineElement,
+    eop: &'a EarthOrientationParameters,
+    min_elevation: f64,
+}
+
+/// The direction of the satellite at an instant, in radians.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    time: DateTime<Utc>,
+    elevation: f64,
+    /// The rate of change of the sine of the elevation, which has the sign of the elevation rate
+    /// and remains finite at the zenith.
+    elevation_rate: f64,
+    azimuth: f64,
+}
+
+impl Sample {
+    fn is_visible(&self, sky: &Sky) -> bool {
+        self.elevation > sky.min_elevation
+    }
+}
+
+impl Sky<'_> {
+    fn sample(&self, time: DateTime<Utc>) -> Result<Sample> {
+        let state = self.tle.propagate_to(time)?;
+        let topocentric = self.observer.topocentric(&state, self.eop);
+        let [east, north, up] = *topocentric.position;
+        let range = topocentric.position.norm();
+        let range_rate = topocentric.position.dot(&topocentric.velocity) / range;
+
+        Ok(Sample {
+            time,
+            elevation: (up / range).asin(),
+            elevation_rate: (topocentric.velocity[2] * range - up * range_rate) / (range * range),
+            azimuth: east.atan2(north).rem_euclid(TAU),
+        })
+    }
+
+    /// Find where `f` changes sign between two samples at which it has opposite signs, giving the
+    /// first sample after the change.
+    fn bisect(
+        &self,
+        mut low: Sample,
+        mut high: Sample,
+        f: impl Fn(&Sample) -> f64,
+    ) -> Result<Sample> {
+        let negative = f(&low) <= 0.0;
+        while high.time - low.time > TIME_TOLERANCE {
+            let middle = self.sample(low.time + (high.time - low.time) / 2)?;
+            if (f(&middle) <= 0.0) == negative {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+    use uom::si::{angle::degree, f64::Length, length::kilometer};
+
+    fn iss() -> TwoLineElement {
+        TwoLineElement::new(
+            "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992",
+            "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767",
+        )
+        .unwrap()
+    }
+
+    fn boulder() -> Observer {
+        Observer::new(
+            Angle::new::<degree>(40.015),
+            Angle::new::<degree>(-105.2705),
+            Length::new::<kilometer>(1.655),
+        )
+    }
+
+    #[test]
+    fn test_passes() -> Result<()> {
+        let tle = iss();
+        let observer = boulder();
+        let start = Utc.with_ymd_and_hms(2020, 5, 27, 0, 0, 0).unwrap();
+        let end = start + Duration::days(1);
+        let passes = observer.passes(&tle, start, end, Angle::new::<degree>(0.0))?;
+        assert!((4..=8).contains(&passes.len()));
+
+        for pass in &passes {
+            assert!(pass.aos < pass.tca && pass.tca < pass.los);
+            assert!(pass.duration() < Duration::minutes(12));
+
+            // The events are where they are claimed to be.
+            let aos = observer.look_angles(&tle.propagate_to(pass.aos)?);
+            assert!(approx_eq!(
+                f64,
+                aos.elevation.get::<degree>(),
+                0.0,
+                epsilon = 1e-3
+            ));
+            assert!(approx_eq!(
+                f64,
+                aos.azimuth.get::<degree>(),
+                pass.aos_azimuth.get::<degree>(),
+                epsilon = 1e-9
+            ));
+            let los = observer.look_angles(&tle.propagate_to(pass.los)?);
+            assert!(approx_eq!(
+                f64,
+                los.elevation.get::<degree>(),
+                0.0,
+                epsilon = 1e-3
+            ));
+            for offset in [-1, 1] {
+                let t = pass.tca + Duration::seconds(offset);
+                let look = observer.look_angles(&tle.propagate_to(t)?);
+                assert!(look.elevation < pass.max_elevation);
+            }
+        }
+
+        // Sampling every ten seconds finds the same passes, to within the sampling interval.
+        let mut visible = Vec::new();
+        let mut t = start;
+        while t <= end {
+            let look = observer.look_angles(&tle.propagate_to(t)?);
+            if look.elevation.get::<degree>() > 0.0 {
+                visible.push(t);
+            }
+            t += Duration::seconds(10);
+        }
+        let rises: Vec<_> = visible
+            .iter()
+            .enumerate()
+            .filter(|(i, t)| *i == 0 || **t - visible[i - 1] > Duration::seconds(10))
+            .map(|(_, t)| *t)
+            .collect();
+        assert_eq!(rises.len(), passes.len());
+        for (rise, pass) in rises.iter().zip(&passes) {
+            assert!(*rise - pass.aos >= Duration::zero());
+            assert!(*rise - pass.aos <= Duration::seconds(10));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimum_elevation() -> Result<()> {
+        let tle = iss();
+        let observer = boulder();
+        let start = Utc.with_ymd_and_hms(2020, 5, 27, 0, 0, 0).unwrap();
+        let end = start + Duration::days(1);
+        let all = observer.passes(&tle, start, end, Angle::new::<degree>(0.0))?;
+        let high = observer.passes(&tle, start, end, Angle::new::<degree>(30.0))?;
+        assert!(!high.is_empty() && high.len() < all.len());
+
+        for pass in &high {
+            assert!(pass.max_elevation.get::<degree>() > 30.0);
+            let aos = observer.look_angles(&tle.propagate_to(pass.aos)?);
+            assert!(approx_eq!(
+                f64,
+                aos.elevation.get::<degree>(),
+                30.0,
+                epsilon = 1e-3
+            ));
+
+            // The same culmination is found whatever the threshold.
+            let full = all.iter().find(|p| p.aos < pass.aos && pass.los < p.los);
+            let full = full.expect("a high pass lies within a full pass");
+            assert!((full.tca - pass.tca).num_milliseconds().abs() <= 2);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_earth_orientation() -> Result<()> {
+        let tle = iss();
+        let observer = boulder();
+        let start = Utc.with_ymd_and_hms(2020, 5, 27, 0, 0, 0).unwrap();
+        let end = start + Duration::days(1);
+        let horizon = Angle::new::<degree>(0.0);
+        let passes = observer.passes(&tle, start, end, horizon)?;
+        let default = EarthOrientationParameters::default();
+        assert_eq!(
+            observer.passes_with(&tle, start, end, horizon, &default)?,
+            passes
+        );
+
+        // The last day of the table is used beyond its end, so UT1 is ahead of UTC by over half a
+        // second and each event moves by up to a few seconds.
+        let eop = EarthOrientationParameters::from_finals_file("test_data/finals2000A.txt")?;
+        let shifted = observer.passes_with(&tle, start, end, horizon, &eop)?;
+        assert_eq!(shifted.len(), passes.len());
+        for (pass, shifted) in passes.iter().zip(&shifted) {
+            assert_ne!(pass.aos, shifted.aos);
+            assert!((pass.aos - shifted.aos).abs() < Duration::seconds(5));
+            assert!((pass.los - shifted.los).abs() < Duration::seconds(5));
+
+            let aos = observer.look_angles_with(&tle.propagate_to(shifted.aos)?, &eop);
+            assert!(approx_eq!(
+                f64,
+                aos.elevation.get::<degree>(),
+                0.0,
+                epsilon = 1e-3
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_window_edges() -> Result<()> {
+        let tle = iss();
+        let observer = boulder();
+        let start = Utc.with_ymd_and_hms(2020, 5, 27, 0, 0, 0).unwrap();
+        let passes = observer.passes(
+            &tle,
+            start,
+            start + Duration::days(1),
+            Angle::new::<degree>(0.0),
+        )?;
+        let pass = passes[0];
+
+        // A window opening and closing mid-pass gives the part of the pass within it.
+        let middle = pass.aos + pass.duration() / 2;
+        let cut = observer.passes(&tle, middle, pass.los, Angle::new::<degree>(0.0))?;
+        assert_eq!(cut.len(), 1);
+        assert_eq!(cut[0].aos, middle);
+        assert!((cut[0].los - pass.los).abs() <= TIME_TOLERANCE);
+
+        let cut = observer.passes(&tle, pass.aos, middle, Angle::new::<degree>(0.0))?;
+        assert_eq!(cut.len(), 1);
+        assert_eq!(cut[0].los, middle);
+
+        assert!(observer
+            .passes(&tle, middle, middle, Angle::new::<degree>(0.0))?
+            .is_empty());
+        Ok(())
+    }
+}
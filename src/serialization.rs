@@ -0,0 +1,419 @@
+//! `Serialize` and `Deserialize` implementations for element sets, state vectors and orbital
+//! elements.
+//!
+//! Quantities are written as plain numbers whose unit is part of the field name, such as
+//! `semimajor_axis_km`, so that the output can be read without knowing this crate. Element sets
+//! follow the established text forms instead: a [TwoLineElement] is written as its two lines, and
+//! an [OrbitMeanElements] as the CCSDS OMM keywords of the general perturbations JSON format.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use uom::si::{
+    angle::degree,
+    angular_velocity::revolution_per_hour,
+    f64::{Angle, Length},
+    length::kilometer,
+};
+
+use crate::{
+    ClassicalOrbitalElements, Frame, GravityModel, OrbitMeanElements, ParseMode,
+    PropagationOptions, StateVector, TwoLineElement,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "GravityModel", rename_all = "SCREAMING_SNAKE_CASE")]
+enum GravityModelName {
+    Wgs72Old,
+    Wgs72,
+    Wgs84,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StateVectorFields {
+    epoch: DateTime<Utc>,
+    frame: String,
+    position_km: [f64; 3],
+    velocity_km_s: [f64; 3],
+    #[serde(default, with = "GravityModelName")]
+    gravity_model: GravityModel,
+}
+
+/// The state is written with the name of its frame, which is checked when it is read back.
+///
+/// Orbital elements are not written, and are derived again from the position and velocity with
+/// the gravity model, which is written as `WGS72_OLD`, `WGS72` or `WGS84`. A state without one is
+/// read with the default model.
+impl<F: Frame> Serialize for StateVector<F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StateVectorFields {
+            epoch: self.epoch,
+            frame: F::NAME.to_owned(),
+            position_km: self.position.into(),
+            velocity_km_s: self.velocity.into(),
+            gravity_model: self.gravity_model,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, F: Frame> Deserialize<'de> for StateVector<F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = StateVectorFields::deserialize(deserializer)?;
+        if fields.frame != F::NAME {
+            return Err(de::Error::custom(format!(
+                "Expected a state in the {} frame, got {}",
+                F::NAME,
+                fields.frame
+            )));
+        }
+        Ok(StateVector::from_vectors_with_gravity_model(
+            fields.epoch,
+            fields.position_km.into(),
+            fields.velocity_km_s.into(),
+            fields.gravity_model,
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ClassicalOrbitalElementsFields {
+    semilatus_rectum_km: f64,
+    semimajor_axis_km: f64,
+    eccentricity: f64,
+    inclination_deg: f64,
+    raan_deg: f64,
+    argument_of_perigee_deg: f64,
+    true_anomaly_deg: f64,
+    mean_anomaly_deg: f64,
+    argument_of_latitude_deg: f64,
+    true_longitude_deg: f64,
+    longitude_of_periapsis_deg: f64,
+}
+
+/// Lengths are written in km and angles in degrees.
+impl Serialize for ClassicalOrbitalElements {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ClassicalOrbitalElementsFields {
+            semilatus_rectum_km: self.semilatus_rectum.get::<kilometer>(),
+            semimajor_axis_km: self.semimajor_axis.get::<kilometer>(),
+            eccentricity: self.eccentricity,
+            inclination_deg: self.inclination.get::<degree>(),
+            raan_deg: self.raan.get::<degree>(),
+            argument_of_perigee_deg: self.argument_of_perigee.get::<degree>(),
+            true_anomaly_deg: self.true_anomaly.get::<degree>(),
+            mean_anomaly_deg: self.mean_anomaly.get::<degree>(),
+            argument_of_latitude_deg: self.argument_of_latitude.get::<degree>(),
+            true_longitude_deg: self.true_longitude.get::<degree>(),
+            longitude_of_periapsis_deg: self.longitude_of_periapsis.get::<degree>(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClassicalOrbitalElements {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = ClassicalOrbitalElementsFields::deserialize(deserializer)?;
+        Ok(ClassicalOrbitalElements {
+            semilatus_rectum: Length::new::<kilometer>(fields.semilatus_rectum_km),
+            semimajor_axis: Length::new::<kilometer>(fields.semimajor_axis_km),
+            eccentricity: fields.eccentricity,
+            inclination: Angle::new::<degree>(fields.inclination_deg),
+            raan: Angle::new::<degree>(fields.raan_deg),
+            argument_of_perigee: Angle::new::<degree>(fields.argument_of_perigee_deg),
+            true_anomaly: Angle::new::<degree>(fields.true_anomaly_deg),
+            mean_anomaly: Angle::new::<degree>(fields.mean_anomaly_deg),
+            argument_of_latitude: Angle::new::<degree>(fields.argument_of_latitude_deg),
+            true_longitude: Angle::new::<degree>(fields.true_longitude_deg),
+            longitude_of_periapsis: Angle::new::<degree>(fields.longitude_of_periapsis_deg),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TwoLineElementFields {
+    line1: String,
+    line2: String,
+}
+
+/// The element set is written as its two lines of text, and read back in [ParseMode::Strict].
+///
+/// Propagation options are not written, and the defaults are used when reading. Use [as_omm] to
+/// write the OMM keywords instead, which can hold any catalog number.
+impl Serialize for TwoLineElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let [line1, line2] = self.to_lines().map_err(ser::Error::custom)?;
+        TwoLineElementFields { line1, line2 }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TwoLineElement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fields = TwoLineElementFields::deserialize(deserializer)?;
+        TwoLineElement::parse(
+            &fields.line1,
+            &fields.line2,
+            ParseMode::Strict,
+            PropagationOptions::default(),
+        )
+        .map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct OmmKeywords<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_id: Option<&'a str>,
+    epoch: String,
+    mean_motion: f64,
+    eccentricity: f64,
+    inclination: f64,
+    ra_of_asc_node: f64,
+    arg_of_pericenter: f64,
+    mean_anomaly: f64,
+    ephemeris_type: u8,
+    classification_type: char,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    norad_cat_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    element_set_no: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rev_at_epoch: Option<u32>,
+    bstar: f64,
+    mean_motion_dot: f64,
+    mean_motion_ddot: f64,
+}
+
+/// The message is written with the keywords and units of the general perturbations JSON format
+/// served by CelesTrak, with numbers as JSON numbers.
+impl Serialize for OrbitMeanElements {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        OmmKeywords {
+            object_name: self.object_name.as_deref(),
+            object_id: self.object_id.as_deref(),
+            epoch: self.epoch.format("%Y-%m-%dT%H:%M:%S%.9f").to_string(),
+            mean_motion: self.mean_motion.get::<revolution_per_hour>() * 24.0,
+            eccentricity: self.eccentricity,
+            inclination: self.inclination.get::<degree>(),
+            ra_of_asc_node: self.raan.get::<degree>(),
+            arg_of_pericenter: self.argument_of_pericenter.get::<degree>(),
+            mean_anomaly: self.mean_anomaly.get::<degree>(),
+            ephemeris_type: self.ephemeris_type,
+            classification_type: self.classification_type,
+            norad_cat_id: self.norad_cat_id,
+            element_set_no: self.element_set_no,
+            rev_at_epoch: self.rev_at_epoch,
+            bstar: self.bstar,
+            mean_motion_dot: self.mean_motion_dot,
+            mean_motion_ddot: self.mean_motion_ddot,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Keyword {
+    Text(String),
+    Number(f64),
+}
+
+/// Values may be numbers or strings, as in [OrbitMeanElements::from_keywords].
+impl<'de> Deserialize<'de> for OrbitMeanElements {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let keywords: HashMap<String, Option<Keyword>> = HashMap::deserialize(deserializer)?;
+        let keywords: HashMap<String, String> = keywords
+            .into_iter()
+            .filter_map(|(key, value)| match value? {
+                Keyword::Text(s) => Some((key, s)),
+                Keyword::Number(n) => Some((key, n.to_string())),
+            })
+            .collect();
+        OrbitMeanElements::from_keywords(|k| keywords.get(k).map(String::as_str))
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Serialize a [TwoLineElement] as the keywords of an [OrbitMeanElements] rather than its lines,
+/// for use with `#[serde(with = "sgp4_rs::as_omm")]`.
+///
+/// ```
+/// # use sgp4_rs::TwoLineElement;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Satellite {
+///     #[serde(with = "sgp4_rs::as_omm")]
+///     elements: TwoLineElement,
+/// }
+/// ```
+pub mod as_omm {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        tle: &TwoLineElement,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        tle.to_omm().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TwoLineElement, D::Error> {
+        let omm = OrbitMeanElements::deserialize(deserializer)?;
+        TwoLineElement::from_omm(&omm).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+    use serde_json::json;
+
+    use crate::{Itrf, Teme};
+
+    const LINE1: &str = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+    const LINE2: &str = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+
+    #[test]
+    fn test_state_vector() -> serde_json::Result<()> {
+        let epoch = Utc.with_ymd_and_hms(2020, 5, 27, 12, 0, 0).unwrap();
+        let state = StateVector::new(epoch, [6778.0, 100.0, -20.0], [0.1, 7.6, 0.5]);
+
+        let value = serde_json::to_value(state)?;
+        assert_eq!(
+            value,
+            json!({
+                "epoch": "2020-05-27T12:00:00Z",
+                "frame": "TEME",
+                "position_km": [6778.0, 100.0, -20.0],
+                "velocity_km_s": [0.1, 7.6, 0.5],
+                "gravity_model": "WGS84",
+            })
+        );
+
+        let read: StateVector<Teme> = serde_json::from_value(value.clone())?;
+        assert_eq!(read.epoch, epoch);
+        assert_eq!(read.position, state.position);
+        assert_eq!(read.velocity, state.velocity);
+        assert_eq!(read.coe.semimajor_axis, state.coe.semimajor_axis);
+
+        // The gravity model of the elements is kept, and is optional.
+        let wgs72 = StateVector::with_gravity_model(
+            epoch,
+            [6778.0, 100.0, -20.0],
+            [0.1, 7.6, 0.5],
+            GravityModel::Wgs72,
+        );
+        let read: StateVector<Teme> = serde_json::from_value(serde_json::to_value(wgs72)?)?;
+        assert_eq!(read.gravity_model, GravityModel::Wgs72);
+        assert_eq!(read.coe.semimajor_axis, wgs72.coe.semimajor_axis);
+        let mut unspecified = value.clone();
+        unspecified.as_object_mut().unwrap().remove("gravity_model");
+        let read: StateVector<Teme> = serde_json::from_value(unspecified)?;
+        assert_eq!(read.gravity_model, GravityModel::Wgs84);
+
+        // A state in one frame cannot be read as another.
+        assert!(serde_json::from_value::<StateVector<Itrf>>(value).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_classical_orbital_elements() -> serde_json::Result<()> {
+        let epoch = Utc.with_ymd_and_hms(2020, 5, 27, 12, 0, 0).unwrap();
+        let coe = StateVector::new(epoch, [6778.0, 100.0, -20.0], [0.1, 7.6, 0.5]).coe;
+
+        let value = serde_json::to_value(coe)?;
+        assert!(approx_eq!(
+            f64,
+            value["semimajor_axis_km"].as_f64().unwrap(),
+            coe.semimajor_axis.get::<kilometer>()
+        ));
+        assert!(approx_eq!(
+            f64,
+            value["inclination_deg"].as_f64().unwrap(),
+            coe.inclination.get::<degree>()
+        ));
+
+        let read: ClassicalOrbitalElements = serde_json::from_value(value)?;
+        assert!(approx_eq!(
+            f64,
+            read.raan.get::<degree>(),
+            coe.raan.get::<degree>(),
+            epsilon = 1e-12
+        ));
+        assert_eq!(read.eccentricity, coe.eccentricity);
+        Ok(())
+    }
+
+    #[test]
+    fn test_two_line_element() -> serde_json::Result<()> {
+        let tle = TwoLineElement::new(LINE1, LINE2).unwrap();
+
+        let value = serde_json::to_value(&tle)?;
+        assert_eq!(value, json!({ "line1": LINE1, "line2": LINE2 }));
+
+        let read: TwoLineElement = serde_json::from_value(value)?;
+        assert_eq!(read.to_lines().unwrap(), [LINE1, LINE2]);
+
+        // Bad lines are rejected, rather than producing an element set which cannot propagate.
+        let bad = json!({ "line1": LINE1, "line2": LINE1 });
+        assert!(serde_json::from_value::<TwoLineElement>(bad).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_two_line_element_as_omm() -> serde_json::Result<()> {
+        #[derive(Serialize, Deserialize)]
+        struct Satellite {
+            #[serde(with = "as_omm")]
+            elements: TwoLineElement,
+        }
+
+        let tle = TwoLineElement::new(LINE1, LINE2).unwrap();
+        let value = serde_json::to_value(Satellite { elements: tle })?;
+        let omm = &value["elements"];
+        assert_eq!(omm["OBJECT_ID"], "1998-067A");
+        assert_eq!(omm["EPOCH"], "2020-05-27T05:06:44.452800000");
+        assert_eq!(omm["NORAD_CAT_ID"], 25544);
+        assert!(approx_eq!(
+            f64,
+            omm["MEAN_MOTION"].as_f64().unwrap(),
+            15.49396855,
+            epsilon = 1e-12
+        ));
+
+        let read: Satellite = serde_json::from_value(value)?;
+        assert_eq!(read.elements.to_lines().unwrap(), [LINE1, LINE2]);
+
+        // The epoch of a message is kept to the nanosecond.
+        let mut omm = read.elements.to_omm();
+        omm.epoch += chrono::Duration::nanoseconds(123_456_789);
+        let read: OrbitMeanElements = serde_json::from_value(serde_json::to_value(&omm)?)?;
+        assert_eq!(read.epoch, omm.epoch);
+        assert_eq!(read, omm);
+
+        // Space-Track gives its values as strings.
+        let omm: OrbitMeanElements = serde_json::from_value(json!({
+            "OBJECT_NAME": "ISS (ZARYA)",
+            "EPOCH": "2020-05-27T05:06:44.452800",
+            "MEAN_MOTION": "15.49396855",
+            "ECCENTRICITY": "0.000257",
+            "INCLINATION": "51.6435",
+            "RA_OF_ASC_NODE": "92.2789",
+            "ARG_OF_PERICENTER": "358.0648",
+            "MEAN_ANOMALY": "144.9972",
+            "NORAD_CAT_ID": "25544",
+            "BSTAR": null,
+        }))?;
+        assert_eq!(omm.object_name.as_deref(), Some("ISS (ZARYA)"));
+        assert_eq!(omm.norad_cat_id, Some(25544));
+        assert_eq!(omm.bstar, 0.0);
+        Ok(())
+    }
+}
@@ -8,10 +8,11 @@ use std::ffi::{CString, NulError};
 use std::os::raw::{c_char, c_double, c_int, c_long};
 
 use chrono::prelude::*;
+use chrono::Duration;
 
 use thiserror::Error;
 
-#[derive(Debug, Error, PartialEq)]
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
 pub enum Error {
     #[error(transparent)]
     CStringNul(#[from] NulError),
@@ -79,7 +80,6 @@ impl InputType {
     }
 }
 
-#[allow(dead_code)]
 #[derive(Default)]
 pub(crate) enum OperationMode {
     AirForceSpaceCenter,
@@ -101,7 +101,6 @@ impl OperationMode {
 const EPSILON: f64 = 0.000_000_1;
 
 #[repr(C)]
-#[allow(dead_code, non_camel_case_types)]
 #[derive(Copy, Clone, Debug)]
 pub(crate) enum GravitationalConstant {
     Wgs72Old,
@@ -113,8 +112,8 @@ pub(crate) enum GravitationalConstant {
 #[allow(dead_code)]
 #[derive(Default, Clone, Copy, Debug)]
 pub(crate) struct OrbitalElementSet {
-    catalog_number: c_long, // satnum
-    epoch_year: c_int,      // epochyr
+    pub(crate) catalog_number: c_long, // satnum
+    epoch_year: c_int,                 // epochyr
 
     // Unused?
     epochtynumrev: c_int,
@@ -373,10 +372,6 @@ impl PartialEq for OrbitalElementSet {
 }
 
 impl OrbitalElementSet {
-    pub fn epoch(&self) -> DateTime<Utc> {
-        julian_day_to_datetime(self.julian_date_at_epoch)
-    }
-
     pub(crate) fn mean_motion(&self) -> f64 {
         self.mean_motion as _
     }
@@ -395,35 +390,46 @@ impl OrbitalElementSet {
     }
 }
 
-pub(crate) fn julian_day_to_datetime(jd: c_double) -> DateTime<Utc> {
-    let mut year = c_int::default();
-    let mut month = c_int::default();
-    let mut day = c_int::default();
-    let mut hour = c_int::default();
-    let mut minute = c_int::default();
-    let mut second = c_double::default();
-
-    unsafe {
-        invjday(
-            jd,
-            &mut year,
-            &mut month,
-            &mut day,
-            &mut hour,
-            &mut minute,
-            &mut second,
-        );
+/// Julian date of the Unix epoch, 1 Jan 1970 00:00 UTC.
+const UNIX_EPOCH_JD: c_double = 2440587.5;
+
+const NANOSECONDS_PER_DAY: c_double = 86_400e9;
+
+/// Convert a two-part Julian date to a date and time, to the nearest nanosecond.
+///
+/// The parts may be split in any way, but precision is best when the first holds the whole days
+/// and the second the fraction of the day. Gives `None` for dates outside the range of `DateTime`.
+pub(crate) fn split_julian_day_to_datetime(
+    day: c_double,
+    fraction: c_double,
+) -> Option<DateTime<Utc>> {
+    if !(day + fraction).is_finite() {
+        return None;
     }
 
-    Utc.with_ymd_and_hms(
-        year,
-        month as u32,
-        day as u32,
-        hour as u32,
-        minute as u32,
-        second as u32,
-    )
-    .unwrap()
+    // Whole days are removed before scaling, so that the nanoseconds are computed from a value
+    // smaller than a day rather than from the full Julian date.
+    let days = (day - UNIX_EPOCH_JD).floor() + fraction.floor();
+    let remainder = (day - UNIX_EPOCH_JD).rem_euclid(1.0) + fraction.rem_euclid(1.0);
+    let nanoseconds = (remainder * NANOSECONDS_PER_DAY).round() as i64;
+
+    DateTime::UNIX_EPOCH
+        .checked_add_signed(Duration::try_days(days as i64)?)?
+        .checked_add_signed(Duration::nanoseconds(nanoseconds))
+}
+
+/// Convert a date and time to a two-part Julian date.
+///
+/// The first part is the Julian date of the preceding midnight and the second the fraction of the
+/// day since then, which together keep the time to well under a nanosecond.
+pub(crate) fn datetime_to_split_julian_day(d: DateTime<Utc>) -> (c_double, c_double) {
+    let midnight = d.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let since_midnight = d - midnight;
+    let fraction = (since_midnight.num_seconds() as c_double * 1e9
+        + since_midnight.subsec_nanos() as c_double)
+        / NANOSECONDS_PER_DAY;
+
+    (datetime_to_julian_day(midnight), fraction)
 }
 
 pub(crate) fn datetime_to_julian_day(d: DateTime<Utc>) -> c_double {
@@ -436,7 +442,7 @@ pub(crate) fn datetime_to_julian_day(d: DateTime<Utc>) -> c_double {
             d.day() as c_int,
             d.hour() as c_int,
             d.minute() as c_int,
-            d.second() as c_double,
+            d.second() as c_double + d.nanosecond() as c_double * 1e-9,
             &mut jd,
         );
     }
@@ -477,6 +483,73 @@ pub(crate) fn to_orbital_elements(
     satrec.into_validated_result()
 }
 
+/// Mean elements used to initialize SGP4 directly, without going through TLE text.
+///
+/// Units follow the conventions of the underlying library: angles in radians and mean motion in
+/// radians per minute.
+#[derive(Debug)]
+pub(crate) struct MeanElements {
+    pub catalog_number: c_long,
+    pub epoch: DateTime<Utc>,
+    pub bstar: c_double, // drag term                      1/earth radii
+    pub ndot: c_double,  // first derivative of mean motion  rad/min^2
+    pub nddot: c_double, // second derivative of mean motion rad/min^3
+    pub ecc: c_double,   // eccentricity
+    pub argp: c_double,  // argument of perigee            0.0  to 2pi rad
+    pub incl: c_double,  // inclination                    0.0  to pi rad
+    pub m: c_double,     // mean anomaly                   0.0  to 2pi rad
+    pub no: c_double,    // mean motion                    rad/min
+    pub omega: c_double, // longitude of ascending node    0.0  to 2pi rad
+}
+
+/// Julian date of the SGP4 epoch, 0 Jan 1950 00:00 UTC.
+const SGP4_EPOCH_JD: c_double = 2433281.5;
+
+pub(crate) fn init_orbital_elements(
+    me: &MeanElements,
+    om: OperationMode,
+    gc: GravitationalConstant,
+) -> Result<OrbitalElementSet, Error> {
+    let grav_consts = gravitational_constants(gc);
+    let (jd, jd_fraction) = datetime_to_split_julian_day(me.epoch);
+    let start_of_year = Utc
+        .with_ymd_and_hms(me.epoch.year(), 1, 1, 0, 0, 0)
+        .unwrap();
+
+    let mut satrec = OrbitalElementSet {
+        catalog_number: me.catalog_number,
+        epoch_year: me.epoch.year() % 100,
+        epoch_days: 1.0 + (jd - datetime_to_julian_day(start_of_year)) + jd_fraction,
+        julian_date_at_epoch: jd + jd_fraction,
+        mean_motion_first_derivative: me.ndot,
+        mean_motion_second_derivative: me.nddot,
+        ..Default::default()
+    };
+
+    satrec.semi_major_axis = (me.no * grav_consts.tumin).powf(-2.0 / 3.0);
+    satrec.altitude_of_apoapsis = satrec.semi_major_axis * (1.0 + me.ecc) - 1.0;
+    satrec.altitude_of_periapsis = satrec.semi_major_axis * (1.0 - me.ecc) - 1.0;
+
+    unsafe {
+        sgp4init(
+            gc,
+            om.to_char(),
+            me.catalog_number as c_int,
+            (jd - SGP4_EPOCH_JD) + jd_fraction,
+            me.bstar,
+            me.ecc,
+            me.argp,
+            me.incl,
+            me.m,
+            me.no,
+            me.omega,
+            &mut satrec,
+        );
+    }
+
+    satrec.into_validated_result()
+}
+
 type Vec3 = [c_double; 3];
 type VectorPair = (Vec3, Vec3);
 
@@ -526,8 +599,12 @@ pub(crate) struct ClassicalOrbitalElements {
 }
 
 #[allow(clippy::many_single_char_names)]
-pub(crate) fn to_classical_elements(r: &Vec3, v: &Vec3) -> ClassicalOrbitalElements {
-    let grav_consts = gravitational_constants();
+pub(crate) fn to_classical_elements(
+    r: &Vec3,
+    v: &Vec3,
+    gc: GravitationalConstant,
+) -> ClassicalOrbitalElements {
+    let grav_consts = gravitational_constants(gc);
 
     let mut p: c_double = 0.0;
     let mut a: c_double = 0.0;
@@ -576,8 +653,8 @@ pub(crate) fn to_classical_elements(r: &Vec3, v: &Vec3) -> ClassicalOrbitalEleme
 }
 
 pub(crate) fn datetime_to_gstime(d: DateTime<Utc>) -> c_double {
-    let jd = datetime_to_julian_day(d);
-    unsafe { gstime(jd) }
+    let (jd, fraction) = datetime_to_split_julian_day(d);
+    unsafe { gstime(jd + fraction) }
 }
 
 pub(crate) struct GravitationalConstants {
@@ -591,7 +668,7 @@ pub(crate) struct GravitationalConstants {
     pub j3oj2: c_double,
 }
 
-pub(crate) fn gravitational_constants() -> GravitationalConstants {
+pub(crate) fn gravitational_constants(gc: GravitationalConstant) -> GravitationalConstants {
     let mut consts = GravitationalConstants {
         tumin: 0.0,
         mu: 0.0,
@@ -604,7 +681,7 @@ pub(crate) fn gravitational_constants() -> GravitationalConstants {
     };
     unsafe {
         getgravconst(
-            GravitationalConstant::Wgs84,
+            gc,
             &mut consts.tumin,
             &mut consts.mu,
             &mut consts.radiusearthkm,
@@ -887,6 +964,31 @@ mod tests {
         assert_eq!(satrec_copy.error, 6)
     }
 
+    #[test]
+    fn test_split_julian_day() {
+        let t = Utc
+            .with_ymd_and_hms(2020, 5, 27, 5, 6, 44)
+            .unwrap()
+            .with_nanosecond(452_800_001)
+            .unwrap();
+        let (day, fraction) = datetime_to_split_julian_day(t);
+        assert_eq!(day, 2458996.5);
+        assert_eq!(split_julian_day_to_datetime(day, fraction), Some(t));
+        // The parts may be split unevenly.
+        assert_eq!(
+            split_julian_day_to_datetime(day - 1.25, fraction + 1.25),
+            Some(t)
+        );
+
+        let t = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(split_julian_day_to_datetime(2451545.0, 0.0), Some(t));
+
+        // Dates beyond the range of chrono are rejected rather than overflowing.
+        assert_eq!(split_julian_day_to_datetime(1e12, 0.0), None);
+        assert_eq!(split_julian_day_to_datetime(-1e12, 0.0), None);
+        assert_eq!(split_julian_day_to_datetime(f64::NAN, 0.0), None);
+    }
+
     #[test]
     fn test_close() {
         assert!(!close(0.1, 0.3));
@@ -0,0 +1,271 @@
+//! Angles describing the rotation of the Earth.
+//!
+//! Greenwich mean sidereal time is the IAU 1982 model used throughout SGP4, apparent sidereal time
+//! adds the equation of the equinoxes to it, and the Earth rotation angle is its IAU 2000
+//! replacement measured from the celestial intermediate origin. Each is an angle in radians, from 0
+//! to 2π, rather than a time as such.
+
+use std::f64::consts::TAU;
+
+use chrono::{DateTime, Utc};
+use uom::si::{angle::radian, f64::Angle};
+
+use crate::nutation::{self, Nutation};
+use crate::{sgp4_sys, Epoch, JulianDay, LeapSecondTable, TimeScale, Ut1Source};
+
+/// Wrapper type representing the angular form of Greenwich Mean Sidereal Time.
+///
+/// This is primarily used to account for the Earth's rotation during conversion between fixed and
+/// inertial coordinate frames. Note that this is an angle measured in radians, and not a "time" as
+/// such. The value may range from 0 to 2π.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GreenwichMeanSiderealTime(f64);
+
+impl GreenwichMeanSiderealTime {
+    /// Compute GMST at an epoch in any time scale, from the corresponding UT1.
+    pub fn at(epoch: &Epoch, leap_seconds: &LeapSecondTable, ut1: &dyn Ut1Source) -> Self {
+        let ut1 = epoch.to_scale(TimeScale::Ut1, leap_seconds, ut1);
+        GreenwichMeanSiderealTime(sgp4_sys::datetime_to_gstime(ut1.time().and_utc()))
+    }
+
+    /// The local mean sidereal time at a longitude, positive to the east.
+    pub fn local(&self, longitude: Angle) -> LocalSiderealTime {
+        LocalSiderealTime::new(self.0, longitude)
+    }
+
+    pub fn as_radians(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_angle(&self) -> Angle {
+        Angle::new::<radian>(self.0)
+    }
+}
+
+/// Computes GMST treating UTC as UT1, which is accurate to within the 0.9s that separates them.
+///
+/// Use [GreenwichMeanSiderealTime::at] with a UT1-UTC source for better accuracy.
+impl From<DateTime<Utc>> for GreenwichMeanSiderealTime {
+    fn from(d: DateTime<Utc>) -> Self {
+        GreenwichMeanSiderealTime(sgp4_sys::datetime_to_gstime(d))
+    }
+}
+
+/// Greenwich Apparent Sidereal Time, the hour angle of the true equinox of date.
+///
+/// This is GMST plus the equation of the equinoxes, from the IAU 1980 nutation with the terms
+/// added by the IAU in 1994. It is the angle that rotates true-of-date coordinates into the
+/// pseudo-Earth-fixed frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GreenwichApparentSiderealTime(f64);
+
+impl GreenwichApparentSiderealTime {
+    /// Compute GAST at an epoch in any time scale, from the corresponding UT1 and TT.
+    pub fn at(epoch: &Epoch, leap_seconds: &LeapSecondTable, ut1: &dyn Ut1Source) -> Self {
+        let gmst = GreenwichMeanSiderealTime::at(epoch, leap_seconds, ut1);
+        let tt = epoch.to_scale(TimeScale::Tt, leap_seconds, ut1);
+        let nutation = Nutation::at(nutation::julian_centuries(tt.into()));
+        GreenwichApparentSiderealTime(
+            (gmst.as_radians() + nutation.equation_of_equinoxes()).rem_euclid(TAU),
+        )
+    }
+
+    /// The local apparent sidereal time at a longitude, positive to the east.
+    pub fn local(&self, longitude: Angle) -> LocalSiderealTime {
+        LocalSiderealTime::new(self.0, longitude)
+    }
+
+    pub fn as_radians(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_angle(&self) -> Angle {
+        Angle::new::<radian>(self.0)
+    }
+}
+
+/// Computes GAST treating UTC as UT1, with the built-in leap second table.
+///
+/// Use [GreenwichApparentSiderealTime::at] with a UT1-UTC source for better accuracy.
+impl From<DateTime<Utc>> for GreenwichApparentSiderealTime {
+    fn from(d: DateTime<Utc>) -> Self {
+        GreenwichApparentSiderealTime::at(&d.into(), &LeapSecondTable::default(), &0.0)
+    }
+}
+
+/// The Earth Rotation Angle of the IAU 2000/2006 models.
+///
+/// This is the angle between the celestial and terrestrial intermediate origins, a linear function
+/// of UT1 which replaces GMST in the CIO-based transformation between GCRS and ITRS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EarthRotationAngle(f64);
+
+impl EarthRotationAngle {
+    /// Compute the ERA at an epoch in any time scale, from the corresponding UT1.
+    pub fn at(epoch: &Epoch, leap_seconds: &LeapSecondTable, ut1: &dyn Ut1Source) -> Self {
+        let ut1 = epoch.to_scale(TimeScale::Ut1, leap_seconds, ut1);
+        let (day, fraction) = JulianDay::from(ut1).parts();
+        // The day part is a midnight, so the fraction of the Julian day is half a day on.
+        let days = (day - nutation::J2000_JD) + fraction;
+        let turns = (fraction + 0.5) + 0.7790572732640 + 0.00273781191135448 * days;
+        EarthRotationAngle((TAU * turns).rem_euclid(TAU))
+    }
+
+    pub fn as_radians(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_angle(&self) -> Angle {
+        Angle::new::<radian>(self.0)
+    }
+}
+
+/// Computes the ERA treating UTC as UT1.
+///
+/// Use [EarthRotationAngle::at] with a UT1-UTC source for better accuracy.
+impl From<DateTime<Utc>> for EarthRotationAngle {
+    fn from(d: DateTime<Utc>) -> Self {
+        EarthRotationAngle::at(&d.into(), &LeapSecondTable::default(), &0.0)
+    }
+}
+
+/// Sidereal time at an observer's meridian, mean or apparent according to the Greenwich sidereal
+/// time it was derived from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalSiderealTime(f64);
+
+impl LocalSiderealTime {
+    fn new(greenwich: f64, longitude: Angle) -> Self {
+        LocalSiderealTime((greenwich + longitude.get::<radian>()).rem_euclid(TAU))
+    }
+
+    pub fn as_radians(&self) -> f64 {
+        self.0
+    }
+
+    pub fn as_angle(&self) -> Angle {
+        Angle::new::<radian>(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{NaiveDate, TimeZone};
+    use float_cmp::approx_eq;
+    use uom::si::angle::degree;
+
+    use crate::EarthOrientationParameters;
+
+    #[test]
+    fn test_gmst_conversion() {
+        let t = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let a: f64 = 100.1218209532; // GMST for 2020-01-01T00:00:00 in degrees
+        let a_rad = a.to_radians();
+        assert!(sgp4_sys::close(
+            GreenwichMeanSiderealTime::from(t).as_radians(),
+            a_rad
+        ));
+    }
+
+    #[test]
+    fn test_gmst_uses_ut1() {
+        let t = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let leap_seconds = LeapSecondTable::default();
+
+        let utc = GreenwichMeanSiderealTime::from(t).as_radians();
+        let same = GreenwichMeanSiderealTime::at(&t.into(), &leap_seconds, &0.0).as_radians();
+        assert!(approx_eq!(f64, utc, same, epsilon = 1e-12));
+
+        // The Earth turns by about 7.29e-5 radians per second.
+        let ut1 = GreenwichMeanSiderealTime::at(&t.into(), &leap_seconds, &-0.5).as_radians();
+        assert!(approx_eq!(f64, utc - ut1, 0.5 * 7.292e-5, epsilon = 1e-8));
+
+        let tai = Epoch::from(t).to_scale(TimeScale::Tai, &leap_seconds, &0.0);
+        let from_tai = GreenwichMeanSiderealTime::at(&tai, &leap_seconds, &0.0).as_radians();
+        assert!(approx_eq!(f64, utc, from_tai, epsilon = 1e-12));
+
+        let eop = EarthOrientationParameters::default();
+        let zero_eop = GreenwichMeanSiderealTime::at(&t.into(), &leap_seconds, &eop).as_radians();
+        assert!(approx_eq!(f64, utc, zero_eop, epsilon = 1e-12));
+    }
+
+    #[test]
+    fn test_apparent_sidereal_time() {
+        // SOFA's iauGst94 at 2006-01-01 takes UT1 and TT to be equal, which they are here with a
+        // UT1-UTC of 65.184s, the value of TT-UTC.
+        let ut1 = Epoch::new(
+            NaiveDate::from_ymd_opt(2006, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            TimeScale::Ut1,
+        );
+        let leap_seconds = LeapSecondTable::default();
+        let gast = GreenwichApparentSiderealTime::at(&ut1, &leap_seconds, &65.184);
+        assert!(approx_eq!(
+            f64,
+            gast.as_radians(),
+            1.754166136020645,
+            epsilon = 1e-9
+        ));
+
+        let gmst = GreenwichMeanSiderealTime::at(&ut1, &leap_seconds, &65.184);
+        assert!(approx_eq!(
+            f64,
+            gmst.as_radians(),
+            1.754174981860675,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[test]
+    fn test_earth_rotation_angle() {
+        // From SOFA's iauEra00.
+        let ut1 = Epoch::new(
+            NaiveDate::from_ymd_opt(2007, 10, 15)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            TimeScale::Ut1,
+        );
+        let era = EarthRotationAngle::at(&ut1, &LeapSecondTable::default(), &0.0);
+        assert!(approx_eq!(
+            f64,
+            era.as_radians(),
+            0.402283724002816,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[test]
+    fn test_local_sidereal_time() {
+        // Vallado, Fundamentals of Astrodynamics and Applications, example 3-5.
+        let t = Utc.with_ymd_and_hms(1992, 8, 20, 12, 14, 0).unwrap();
+        let gmst = GreenwichMeanSiderealTime::from(t);
+        assert!(approx_eq!(
+            f64,
+            gmst.as_angle().get::<degree>(),
+            152.578787886,
+            epsilon = 1e-6
+        ));
+
+        let lst = gmst.local(Angle::new::<degree>(-104.0));
+        assert!(approx_eq!(
+            f64,
+            lst.as_angle().get::<degree>(),
+            48.578787886,
+            epsilon = 1e-6
+        ));
+
+        // Local times stay within a turn.
+        let lst = gmst.local(Angle::new::<degree>(250.0));
+        assert!(approx_eq!(
+            f64,
+            lst.as_angle().get::<degree>(),
+            42.578787886,
+            epsilon = 1e-6
+        ));
+    }
+}
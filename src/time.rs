@@ -0,0 +1,456 @@
+//! Time scales and the conversions between them.
+//!
+//! SGP4 itself works in UTC, but sidereal time and the rotations between reference frames are
+//! defined in terms of UT1 and TT. TAI is the common scale through which every conversion passes:
+//! UTC differs from it by a whole number of leap seconds, TT and GPS time by fixed offsets, and UT1
+//! by the measured UT1-UTC offset on top of UTC.
+
+use std::path::Path;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+
+use crate::{Error, Result};
+
+/// A scale in which the reading of a clock may be given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeScale {
+    /// Coordinated Universal Time, which is kept within 0.9s of UT1 by leap seconds.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, used for the precession and nutation of the Earth's axis.
+    Tt,
+    /// Universal Time, which follows the rotation of the Earth.
+    Ut1,
+    /// GPS system time, which has not followed leap seconds since 1980.
+    Gps,
+}
+
+/// TT runs ahead of TAI by a fixed 32.184s.
+const TT_MINUS_TAI_MICROSECONDS: i64 = 32_184_000;
+
+/// GPS time was equal to UTC when it began in 1980, at which point TAI was 19s ahead.
+const TAI_MINUS_GPS_SECONDS: i64 = 19;
+
+/// The start of GPS week zero, 6 Jan 1980 00:00 in GPS time.
+fn gps_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1980, 1, 6)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
+/// The J2000 reference epoch, 1 Jan 2000 12:00.
+fn j2000() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .unwrap()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+}
+
+const SECONDS_PER_WEEK: i64 = 7 * 86_400;
+
+/// A GPS time given as a week number and the seconds elapsed in that week.
+///
+/// Weeks are counted from 6 Jan 1980 without the 1024 week rollover of the broadcast format.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsWeekTime {
+    pub week: u32,
+    pub seconds: f64,
+}
+
+impl From<GpsWeekTime> for Epoch {
+    fn from(gps: GpsWeekTime) -> Self {
+        let time = gps_epoch()
+            + Duration::weeks(gps.week.into())
+            + Duration::nanoseconds((gps.seconds * 1e9).round() as i64);
+        Epoch::new(time, TimeScale::Gps)
+    }
+}
+
+/// An instant, given as the reading of a clock in a particular time scale.
+///
+/// Leap seconds themselves cannot be represented, so instants during a positive leap second are
+/// converted to the following second of UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Epoch {
+    time: NaiveDateTime,
+    scale: TimeScale,
+}
+
+impl Epoch {
+    pub fn new(time: NaiveDateTime, scale: TimeScale) -> Self {
+        Epoch { time, scale }
+    }
+
+    /// Get the clock reading, in this epoch's time scale.
+    pub fn time(&self) -> NaiveDateTime {
+        self.time
+    }
+
+    pub fn scale(&self) -> TimeScale {
+        self.scale
+    }
+
+    /// Express the same instant in another time scale.
+    pub fn to_scale(
+        &self,
+        scale: TimeScale,
+        leap_seconds: &LeapSecondTable,
+        ut1: &dyn Ut1Source,
+    ) -> Epoch {
+        let tai = self.tai(leap_seconds, ut1);
+        let time = match scale {
+            TimeScale::Tai => tai,
+            TimeScale::Tt => tai + Duration::microseconds(TT_MINUS_TAI_MICROSECONDS),
+            TimeScale::Gps => tai - Duration::seconds(TAI_MINUS_GPS_SECONDS),
+            TimeScale::Utc => leap_seconds.utc_from_tai(tai),
+            TimeScale::Ut1 => {
+                let utc = leap_seconds.utc_from_tai(tai).and_utc();
+                (utc + ut1_offset(ut1, utc)).naive_utc()
+            }
+        };
+        Epoch { time, scale }
+    }
+
+    /// Create an epoch from a number of seconds since the J2000 epoch of 1 Jan 2000 12:00, counted
+    /// in the given time scale.
+    ///
+    /// The J2000 epoch is conventionally a TT reading, but flight software often counts from the
+    /// same reading in another scale, so the scale must be given.
+    pub fn from_seconds_since_j2000(seconds: f64, scale: TimeScale) -> Self {
+        Epoch::new(
+            j2000() + Duration::nanoseconds((seconds * 1e9).round() as i64),
+            scale,
+        )
+    }
+
+    /// Get the number of seconds since the reading 1 Jan 2000 12:00 in this epoch's time scale.
+    ///
+    /// Convert the epoch to [TimeScale::Tt] first to count from the J2000 epoch itself.
+    pub fn seconds_since_j2000(&self) -> f64 {
+        let since = self.time - j2000();
+        since.num_seconds() as f64 + since.subsec_nanos() as f64 * 1e-9
+    }
+
+    /// Express the epoch as a GPS week and seconds of week.
+    ///
+    /// This returns `None` for instants before the start of GPS time.
+    pub fn to_gps_week_time(
+        &self,
+        leap_seconds: &LeapSecondTable,
+        ut1: &dyn Ut1Source,
+    ) -> Option<GpsWeekTime> {
+        let since = self.to_scale(TimeScale::Gps, leap_seconds, ut1).time - gps_epoch();
+        let week = since.num_seconds().div_euclid(SECONDS_PER_WEEK);
+        let seconds = since - Duration::weeks(week);
+        Some(GpsWeekTime {
+            week: week.try_into().ok()?,
+            seconds: seconds.num_seconds() as f64 + seconds.subsec_nanos() as f64 * 1e-9,
+        })
+    }
+
+    /// Get the instant as a UTC date and time.
+    pub fn to_utc(&self, leap_seconds: &LeapSecondTable, ut1: &dyn Ut1Source) -> DateTime<Utc> {
+        self.to_scale(TimeScale::Utc, leap_seconds, ut1)
+            .time
+            .and_utc()
+    }
+
+    fn tai(&self, leap_seconds: &LeapSecondTable, ut1: &dyn Ut1Source) -> NaiveDateTime {
+        match self.scale {
+            TimeScale::Tai => self.time,
+            TimeScale::Tt => self.time - Duration::microseconds(TT_MINUS_TAI_MICROSECONDS),
+            TimeScale::Gps => self.time + Duration::seconds(TAI_MINUS_GPS_SECONDS),
+            TimeScale::Utc => leap_seconds.tai_from_utc(self.time),
+            TimeScale::Ut1 => {
+                // UT1-UTC changes by a few milliseconds a day, so evaluating it at the UT1 reading
+                // and then once more at the resulting UTC is more than enough.
+                let ut1_time = self.time.and_utc();
+                let utc = ut1_time - ut1_offset(ut1, ut1_time);
+                let utc = ut1_time - ut1_offset(ut1, utc);
+                leap_seconds.tai_from_utc(utc.naive_utc())
+            }
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for Epoch {
+    fn from(d: DateTime<Utc>) -> Self {
+        Epoch::new(d.naive_utc(), TimeScale::Utc)
+    }
+}
+
+/// A source of the offset between UT1 and UTC.
+///
+/// A constant `f64` is itself a source, giving that offset in seconds at every instant; `0.0`
+/// treats UT1 as equal to UTC.
+pub trait Ut1Source {
+    /// Get UT1-UTC, in seconds, at the given instant.
+    fn ut1_minus_utc(&self, utc: DateTime<Utc>) -> f64;
+}
+
+impl Ut1Source for f64 {
+    fn ut1_minus_utc(&self, _utc: DateTime<Utc>) -> f64 {
+        *self
+    }
+}
+
+fn ut1_offset(ut1: &dyn Ut1Source, utc: DateTime<Utc>) -> Duration {
+    Duration::nanoseconds((ut1.ut1_minus_utc(utc) * 1e9).round() as i64)
+}
+
+/// The offset between TAI and UTC, which changes at each leap second.
+///
+/// The table built into the crate is current as of the leap second at the end of 2016. Later
+/// leap seconds can be picked up by loading an updated copy of the IETF `leap-seconds.list` file,
+/// which is distributed with most time zone databases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeapSecondTable {
+    /// The UTC instant at which each offset takes effect, with TAI-UTC in seconds from then on.
+    entries: Vec<(NaiveDateTime, i64)>,
+}
+
+/// The dates from which TAI-UTC took each value, since UTC adopted whole leap seconds in 1972.
+const LEAP_SECONDS: [((i32, u32), i64); 28] = [
+    ((1972, 1), 10),
+    ((1972, 7), 11),
+    ((1973, 1), 12),
+    ((1974, 1), 13),
+    ((1975, 1), 14),
+    ((1976, 1), 15),
+    ((1977, 1), 16),
+    ((1978, 1), 17),
+    ((1979, 1), 18),
+    ((1980, 1), 19),
+    ((1981, 7), 20),
+    ((1982, 7), 21),
+    ((1983, 7), 22),
+    ((1985, 7), 23),
+    ((1988, 1), 24),
+    ((1990, 1), 25),
+    ((1991, 1), 26),
+    ((1992, 7), 27),
+    ((1993, 7), 28),
+    ((1994, 7), 29),
+    ((1996, 1), 30),
+    ((1997, 7), 31),
+    ((1999, 1), 32),
+    ((2006, 1), 33),
+    ((2009, 1), 34),
+    ((2012, 7), 35),
+    ((2015, 7), 36),
+    ((2017, 1), 37),
+];
+
+/// Seconds from the NTP epoch of 1 Jan 1900, used by `leap-seconds.list`, to the Unix epoch.
+const NTP_TO_UNIX_SECONDS: i64 = 2_208_988_800;
+
+impl Default for LeapSecondTable {
+    fn default() -> Self {
+        let entries = LEAP_SECONDS
+            .iter()
+            .map(|&((year, month), offset)| {
+                let start = NaiveDate::from_ymd_opt(year, month, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap();
+                (start, offset)
+            })
+            .collect();
+        LeapSecondTable { entries }
+    }
+}
+
+impl LeapSecondTable {
+    /// Parse the contents of an IETF `leap-seconds.list` file.
+    ///
+    /// Each data line gives a time in seconds since 1900 and the value of TAI-UTC from then on.
+    /// Comment lines, starting with `#`, are ignored.
+    pub fn parse(text: &str) -> Result<LeapSecondTable> {
+        let mut entries = vec![];
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid =
+                || Error::MalformedLeapSecondTable(format!("Invalid entry on line {}", number + 1));
+            let mut values = line.split_whitespace();
+            let ntp_seconds: i64 = values
+                .next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(invalid)?;
+            let offset: i64 = values
+                .next()
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(invalid)?;
+            let start = DateTime::from_timestamp(ntp_seconds - NTP_TO_UNIX_SECONDS, 0)
+                .ok_or_else(invalid)?
+                .naive_utc();
+
+            if entries.last().is_some_and(|(last, _)| *last >= start) {
+                return Err(Error::MalformedLeapSecondTable(format!(
+                    "Entry on line {} is out of order",
+                    number + 1
+                )));
+            }
+            entries.push((start, offset));
+        }
+
+        if entries.is_empty() {
+            return Err(Error::MalformedLeapSecondTable(
+                "No leap seconds were found".to_owned(),
+            ));
+        }
+        Ok(LeapSecondTable { entries })
+    }
+
+    /// Read an IETF `leap-seconds.list` file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<LeapSecondTable> {
+        let text = std::fs::read_to_string(path).map_err(|e| Error::IoError(e.to_string()))?;
+        LeapSecondTable::parse(&text)
+    }
+
+    /// Get TAI-UTC, in seconds, at the given instant.
+    ///
+    /// Dates before the start of the table are given its first offset.
+    pub fn tai_minus_utc(&self, utc: DateTime<Utc>) -> i64 {
+        self.offset_at(utc.naive_utc(), |start, _| start)
+    }
+
+    fn tai_from_utc(&self, utc: NaiveDateTime) -> NaiveDateTime {
+        utc + Duration::seconds(self.offset_at(utc, |start, _| start))
+    }
+
+    fn utc_from_tai(&self, tai: NaiveDateTime) -> NaiveDateTime {
+        // Each offset takes effect at a TAI reading which already includes it.
+        let offset = self.offset_at(tai, |start, offset| start + Duration::seconds(offset));
+        tai - Duration::seconds(offset)
+    }
+
+    /// Find the offset in effect at a time, where `start` gives the time at which each entry
+    /// takes effect on the same scale.
+    fn offset_at(
+        &self,
+        time: NaiveDateTime,
+        start: impl Fn(NaiveDateTime, i64) -> NaiveDateTime,
+    ) -> i64 {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(s, offset)| start(*s, *offset) <= time)
+            .unwrap_or(&self.entries[0])
+            .1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    fn time(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scale_offsets() {
+        let leap_seconds = LeapSecondTable::default();
+        let utc = Epoch::from(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+
+        let convert = |scale| utc.to_scale(scale, &leap_seconds, &0.2).time();
+        assert_eq!(convert(TimeScale::Tai), time(2020, 1, 1, 0, 0, 37));
+        assert_eq!(convert(TimeScale::Gps), time(2020, 1, 1, 0, 0, 18));
+        assert_eq!(
+            convert(TimeScale::Tt),
+            time(2020, 1, 1, 0, 1, 9) + Duration::milliseconds(184)
+        );
+        assert_eq!(
+            convert(TimeScale::Ut1),
+            time(2020, 1, 1, 0, 0, 0) + Duration::milliseconds(200)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let leap_seconds = LeapSecondTable::default();
+        let utc = Epoch::from(Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).unwrap());
+
+        for scale in [
+            TimeScale::Utc,
+            TimeScale::Tai,
+            TimeScale::Tt,
+            TimeScale::Ut1,
+            TimeScale::Gps,
+        ] {
+            let converted = utc.to_scale(scale, &leap_seconds, &-0.4);
+            assert_eq!(converted.scale(), scale);
+            assert_eq!(
+                converted.to_scale(TimeScale::Utc, &leap_seconds, &-0.4),
+                utc
+            );
+        }
+    }
+
+    #[test]
+    fn test_leap_second_boundary() {
+        let leap_seconds = LeapSecondTable::default();
+        let before = Utc.with_ymd_and_hms(2016, 12, 31, 23, 59, 59).unwrap();
+        let after = Utc.with_ymd_and_hms(2017, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(leap_seconds.tai_minus_utc(before), 36);
+        assert_eq!(leap_seconds.tai_minus_utc(after), 37);
+
+        // The leap second itself, 23:59:60 UTC, falls between these two TAI readings.
+        let tai = |t: DateTime<Utc>| {
+            Epoch::from(t)
+                .to_scale(TimeScale::Tai, &leap_seconds, &0.0)
+                .time()
+        };
+        assert_eq!(tai(after) - tai(before), Duration::seconds(2));
+    }
+
+    #[test]
+    fn test_gps_week_time() {
+        let leap_seconds = LeapSecondTable::default();
+        let utc = Epoch::from(Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap());
+
+        // Week 2086 began on Sunday 29 Dec 2019, and GPS time was 18s ahead of UTC.
+        let gps = utc.to_gps_week_time(&leap_seconds, &0.0).unwrap();
+        assert_eq!(gps.week, 2086);
+        assert_eq!(gps.seconds, 3.0 * 86_400.0 + 18.0);
+        assert_eq!(
+            Epoch::from(gps).to_utc(&leap_seconds, &0.0),
+            utc.to_utc(&leap_seconds, &0.0)
+        );
+
+        let before = Epoch::from(Utc.with_ymd_and_hms(1979, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(before.to_gps_week_time(&leap_seconds, &0.0), None);
+    }
+
+    #[test]
+    fn test_seconds_since_j2000() {
+        let epoch = Epoch::new(time(2000, 1, 2, 12, 0, 0), TimeScale::Tt);
+        assert_eq!(epoch.seconds_since_j2000(), 86_400.0);
+        assert_eq!(
+            Epoch::from_seconds_since_j2000(-0.25, TimeScale::Tai).time(),
+            time(2000, 1, 1, 11, 59, 59) + Duration::milliseconds(750)
+        );
+    }
+
+    #[test]
+    fn test_parse_leap_seconds_list() -> Result<()> {
+        let table = LeapSecondTable::from_file("test_data/leap-seconds.list")?;
+        assert_eq!(table, LeapSecondTable::default());
+
+        assert!(LeapSecondTable::parse("# Only a comment\n").is_err());
+        assert!(LeapSecondTable::parse("3692217600 37\n3644697600 36\n").is_err());
+        assert!(LeapSecondTable::parse("3692217600 thirty-seven\n").is_err());
+        Ok(())
+    }
+}
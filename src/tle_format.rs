@@ -0,0 +1,956 @@
+//! Helpers for the fixed-column text format of two line element sets.
+//!
+//! The column layout is described in the CelesTrak TLE FAQ, and the Alpha-5 catalog number scheme
+//! in the Space-Track documentation. Column ranges in this module are zero-based and half-open, so
+//! the FAQ's columns 3-7 are written `2..7`.
+
+use std::ops::Range;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+
+use uom::si::{
+    angle::degree,
+    angular_velocity::revolution_per_hour,
+    f64::{Angle, AngularVelocity},
+};
+
+use crate::{sgp4_sys, Error, OrbitMeanElements, Result, TLE_LINE_LENGTH};
+
+/// Columns 3-7 of both TLE lines hold the satellite catalog number.
+pub(crate) const CATALOG_NUMBER_COLUMNS: Range<usize> = 2..7;
+
+/// The largest catalog number which can be written in the five columns of a TLE.
+pub(crate) const MAX_ALPHA5_CATALOG_NUMBER: u32 = 339_999;
+
+const NANOSECONDS_PER_DAY: u64 = 86_400 * 1_000_000_000;
+
+/// The values of every field of a TLE, in the units used by the text format.
+///
+/// This is kept alongside the initialized SGP4 state so that an element set can be written back
+/// out exactly as it was read.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TleFields {
+    pub catalog_number: u32,
+    pub classification: char,
+    pub international_designator: String,
+    pub epoch: DateTime<Utc>,
+    /// First derivative of mean motion divided by two, in revolutions per day squared.
+    pub mean_motion_dot: f64,
+    /// Second derivative of mean motion divided by six, in revolutions per day cubed.
+    pub mean_motion_ddot: f64,
+    /// SGP4 drag term, in inverse Earth radii.
+    pub bstar: f64,
+    pub ephemeris_type: u8,
+    pub element_set_number: u32,
+    /// Inclination in degrees.
+    pub inclination: f64,
+    /// Right ascension of the ascending node in degrees.
+    pub raan: f64,
+    pub eccentricity: f64,
+    /// Argument of perigee in degrees.
+    pub argument_of_perigee: f64,
+    /// Mean anomaly in degrees.
+    pub mean_anomaly: f64,
+    /// Mean motion in revolutions per day.
+    pub mean_motion: f64,
+    pub revolution_number: u32,
+    /// The sign written before a zero exponent. Producers disagree between `00000-0` and
+    /// `00000+0`, so this is remembered to reproduce the input exactly.
+    pub zero_exponent_sign: char,
+}
+
+/// A problem found while parsing the text of a TLE.
+///
+/// Line numbers are 1 or 2, and column ranges are zero-based and half-open so that they can be
+/// used to slice the offending line directly. Messages give columns in the one-based form of the
+/// TLE documentation.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TleParseError {
+    #[error("Expected two lines, got {0}")]
+    WrongLineCount(usize),
+    #[error("Line {line} is missing")]
+    MissingLine { line: usize },
+    #[error("Line {line} is the wrong length. Expected {expected}, but got {actual}")]
+    WrongLength {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Line {line} has checksum {actual}, but its contents sum to {expected}")]
+    ChecksumMismatch {
+        line: usize,
+        expected: u32,
+        actual: u32,
+    },
+    #[error("Line {line} starts with {found:?} instead of its line number")]
+    WrongLineNumber { line: usize, found: char },
+    #[error(
+        "Invalid {field} in line {line}, columns {}-{}",
+        .columns.start + 1,
+        .columns.end
+    )]
+    InvalidField {
+        line: usize,
+        field: &'static str,
+        columns: Range<usize>,
+    },
+    #[error("Catalog number {line1} in line 1 does not match {line2} in line 2")]
+    CatalogNumberMismatch { line1: u32, line2: u32 },
+    #[error("Elements were rejected by SGP4: {0}")]
+    RejectedElements(sgp4_sys::Error),
+}
+
+/// How strictly the text of a TLE is checked when it is parsed.
+///
+/// In either mode surrounding whitespace, including tabs and line terminators, is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Require exactly 69 columns on each line with a correct checksum.
+    Strict,
+    /// Accept 68 column lines without a checksum, and do not verify checksums. This is how
+    /// [TwoLineElement::new] reads its lines.
+    ///
+    /// [TwoLineElement::new]: crate::TwoLineElement::new
+    #[default]
+    Lenient,
+}
+
+/// Bring a line of a TLE into its standard 69 column form, as accepted by the given mode.
+///
+/// A line without a checksum has one appended, so that the result can always be handed to the
+/// reference parser.
+pub(crate) fn normalize_line(text: &str, line: usize, mode: ParseMode) -> Result<String> {
+    let text = text.trim();
+
+    match (mode, text.len()) {
+        (ParseMode::Lenient, len) if len == TLE_LINE_LENGTH - 1 => {
+            Ok(add_checksum(text.to_owned()))
+        }
+        (ParseMode::Lenient, TLE_LINE_LENGTH) => Ok(text.to_owned()),
+        (ParseMode::Strict, TLE_LINE_LENGTH) => {
+            let columns = TLE_LINE_LENGTH - 1..TLE_LINE_LENGTH;
+            let actual = text
+                .get(columns.clone())
+                .filter(|c| c.is_ascii())
+                .and_then(|c| c.parse().ok())
+                .ok_or(TleParseError::InvalidField {
+                    line,
+                    field: "checksum",
+                    columns,
+                })?;
+            let expected = checksum(text);
+            if actual != expected {
+                return Err(TleParseError::ChecksumMismatch {
+                    line,
+                    expected,
+                    actual,
+                }
+                .into());
+            }
+            Ok(text.to_owned())
+        }
+        (_, actual) => Err(TleParseError::WrongLength {
+            line,
+            expected: TLE_LINE_LENGTH,
+            actual,
+        }
+        .into()),
+    }
+}
+
+impl TleFields {
+    /// Parse the fields of a TLE from its two lines, which must be in their 69 column form.
+    pub fn parse(line1: &str, line2: &str) -> Result<TleFields> {
+        let line1 = Line::new(1, line1)?;
+        let line2 = Line::new(2, line2)?;
+
+        let catalog_number = line1.catalog_number()?;
+        let line2_catalog_number = line2.catalog_number()?;
+        if catalog_number != line2_catalog_number {
+            return Err(TleParseError::CatalogNumberMismatch {
+                line1: catalog_number,
+                line2: line2_catalog_number,
+            }
+            .into());
+        }
+
+        let nddot_field = line1.field(44..52, "second derivative of mean motion")?;
+        let bstar_field = line1.field(53..61, "BSTAR")?;
+        let zero_exponent_sign = [nddot_field, bstar_field]
+            .iter()
+            .find(|f| f.ends_with('0'))
+            .and_then(|f| f.chars().nth(6))
+            .filter(|c| *c == '+')
+            .unwrap_or('-');
+
+        Ok(TleFields {
+            catalog_number,
+            classification: line1
+                .field(7..8, "classification")?
+                .chars()
+                .next()
+                .unwrap_or('U'),
+            international_designator: line1
+                .field(9..17, "international designator")?
+                .trim()
+                .to_owned(),
+            epoch: line1.epoch()?,
+            mean_motion_dot: line1.decimal(33..43, "first derivative of mean motion")?,
+            mean_motion_ddot: line1.exponential(44..52, "second derivative of mean motion")?,
+            bstar: line1.exponential(53..61, "BSTAR")?,
+            ephemeris_type: line1.integer(62..63, "ephemeris type")?,
+            element_set_number: line1.integer(64..68, "element set number")?,
+            inclination: line2.decimal(8..16, "inclination")?,
+            raan: line2.decimal(17..25, "right ascension of the ascending node")?,
+            eccentricity: line2.integer::<u32>(26..33, "eccentricity")? as f64 * 1e-7,
+            argument_of_perigee: line2.decimal(34..42, "argument of perigee")?,
+            mean_anomaly: line2.decimal(43..51, "mean anomaly")?,
+            mean_motion: line2.decimal(52..63, "mean motion")?,
+            revolution_number: line2.integer(63..68, "revolution number")?,
+            zero_exponent_sign,
+        })
+    }
+
+    /// Write the fields out in the standard column layout, including checksums.
+    pub fn format(&self) -> Result<[String; 2]> {
+        let catalog_number = encode_catalog_number(self.catalog_number).ok_or_else(|| {
+            Error::UnrepresentableTwoLineElement(format!(
+                "Catalog number {} is too large for a TLE, the maximum is {}",
+                self.catalog_number, MAX_ALPHA5_CATALOG_NUMBER
+            ))
+        })?;
+        if self.element_set_number > 9999 {
+            return Err(Error::UnrepresentableTwoLineElement(format!(
+                "Element set number {} is too large for a TLE",
+                self.element_set_number
+            )));
+        }
+        if self.revolution_number > 99999 {
+            return Err(Error::UnrepresentableTwoLineElement(format!(
+                "Revolution number {} is too large for a TLE",
+                self.revolution_number
+            )));
+        }
+
+        let line1 = format!(
+            "1 {}{} {:<8} {} {} {} {} {} {:>4}",
+            catalog_number,
+            self.classification,
+            self.international_designator,
+            TleEpoch(self.epoch),
+            fit(
+                format_decimal(self.mean_motion_dot),
+                10,
+                "mean motion derivative"
+            )?,
+            fit(
+                format_exponential(self.mean_motion_ddot, self.zero_exponent_sign),
+                8,
+                "mean motion second derivative"
+            )?,
+            fit(
+                format_exponential(self.bstar, self.zero_exponent_sign),
+                8,
+                "BSTAR"
+            )?,
+            fit(self.ephemeris_type.to_string(), 1, "ephemeris type")?,
+            self.element_set_number,
+        );
+        let line2 = format!(
+            "2 {} {} {} {} {} {} {}{:>5}",
+            catalog_number,
+            fit(format!("{:>8.4}", self.inclination), 8, "inclination")?,
+            fit(format!("{:>8.4}", self.raan), 8, "right ascension")?,
+            fit(
+                format!("{:07}", (self.eccentricity * 1e7).round() as u32),
+                7,
+                "eccentricity"
+            )?,
+            fit(
+                format!("{:>8.4}", self.argument_of_perigee),
+                8,
+                "argument of perigee"
+            )?,
+            fit(format!("{:>8.4}", self.mean_anomaly), 8, "mean anomaly")?,
+            fit(format!("{:>11.8}", self.mean_motion), 11, "mean motion")?,
+            self.revolution_number,
+        );
+
+        Ok([add_checksum(line1), add_checksum(line2)])
+    }
+}
+
+impl TleFields {
+    /// Convert the fields to the units expected by `sgp4init`.
+    pub fn to_mean_elements(&self) -> sgp4_sys::MeanElements {
+        use std::f64::consts::PI;
+        const MINUTES_PER_DAY: f64 = 1440.0;
+
+        sgp4_sys::MeanElements {
+            catalog_number: self.catalog_number.into(),
+            epoch: self.epoch,
+            bstar: self.bstar,
+            ndot: self.mean_motion_dot * 2.0 * PI / MINUTES_PER_DAY.powi(2),
+            nddot: self.mean_motion_ddot * 2.0 * PI / MINUTES_PER_DAY.powi(3),
+            ecc: self.eccentricity,
+            argp: self.argument_of_perigee.to_radians(),
+            incl: self.inclination.to_radians(),
+            m: self.mean_anomaly.to_radians(),
+            no: self.mean_motion * 2.0 * PI / MINUTES_PER_DAY,
+            omega: self.raan.to_radians(),
+        }
+    }
+}
+
+impl From<&OrbitMeanElements> for TleFields {
+    fn from(omm: &OrbitMeanElements) -> Self {
+        TleFields {
+            catalog_number: omm.norad_cat_id.unwrap_or(0),
+            classification: omm.classification_type,
+            international_designator: omm
+                .object_id
+                .as_deref()
+                .map(international_designator)
+                .unwrap_or_default(),
+            epoch: omm.epoch,
+            mean_motion_dot: omm.mean_motion_dot,
+            mean_motion_ddot: omm.mean_motion_ddot,
+            bstar: omm.bstar,
+            ephemeris_type: omm.ephemeris_type,
+            element_set_number: omm.element_set_no.unwrap_or(999),
+            inclination: omm.inclination.get::<degree>(),
+            raan: omm.raan.get::<degree>(),
+            eccentricity: omm.eccentricity,
+            argument_of_perigee: omm.argument_of_pericenter.get::<degree>(),
+            mean_anomaly: omm.mean_anomaly.get::<degree>(),
+            mean_motion: omm.mean_motion.get::<revolution_per_hour>() * 24.0,
+            revolution_number: omm.rev_at_epoch.unwrap_or(0),
+            zero_exponent_sign: '-',
+        }
+    }
+}
+
+impl From<&TleFields> for OrbitMeanElements {
+    fn from(fields: &TleFields) -> Self {
+        OrbitMeanElements {
+            object_name: None,
+            object_id: object_id(&fields.international_designator),
+            epoch: fields.epoch,
+            mean_motion: AngularVelocity::new::<revolution_per_hour>(fields.mean_motion / 24.0),
+            eccentricity: fields.eccentricity,
+            inclination: Angle::new::<degree>(fields.inclination),
+            raan: Angle::new::<degree>(fields.raan),
+            argument_of_pericenter: Angle::new::<degree>(fields.argument_of_perigee),
+            mean_anomaly: Angle::new::<degree>(fields.mean_anomaly),
+            ephemeris_type: fields.ephemeris_type,
+            classification_type: fields.classification,
+            norad_cat_id: Some(fields.catalog_number),
+            element_set_no: Some(fields.element_set_number),
+            rev_at_epoch: Some(fields.revolution_number),
+            bstar: fields.bstar,
+            mean_motion_dot: fields.mean_motion_dot,
+            mean_motion_ddot: fields.mean_motion_ddot,
+        }
+    }
+}
+
+/// An epoch in the TLE form `YYDDD.DDDDDDDD`: a two digit year, then the day of the year with its
+/// fraction.
+///
+/// Two digit years from 57 to 99 are in the 1900s, and the rest in the 2000s. The epoch is written
+/// with a resolution of 1e-8 days, about a millisecond, and is read back exactly to the nanosecond.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TleEpoch(pub DateTime<Utc>);
+
+impl std::str::FromStr for TleEpoch {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<TleEpoch> {
+        let s = s.trim();
+        s.get(..2)
+            .filter(|_| s.is_ascii())
+            .and_then(|_| parse_epoch(s))
+            .map(TleEpoch)
+            .ok_or_else(|| Error::MalformedEpoch(format!("{:?} is not a TLE epoch", s)))
+    }
+}
+
+impl std::fmt::Display for TleEpoch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Rounding to the resolution of the format may carry into the next day.
+        let nanoseconds =
+            self.0.num_seconds_from_midnight() as u64 * 1_000_000_000 + self.0.nanosecond() as u64;
+        let mut day_fraction = (nanoseconds * 100 + NANOSECONDS_PER_DAY / 2_000_000)
+            / (NANOSECONDS_PER_DAY / 1_000_000);
+        let mut date = self.0.date_naive();
+        if day_fraction >= 100_000_000 {
+            day_fraction -= 100_000_000;
+            date += Duration::days(1);
+        }
+
+        write!(
+            f,
+            "{:02}{:03}.{:08}",
+            date.year() % 100,
+            date.ordinal(),
+            day_fraction
+        )
+    }
+}
+
+impl From<DateTime<Utc>> for TleEpoch {
+    fn from(d: DateTime<Utc>) -> Self {
+        TleEpoch(d)
+    }
+}
+
+impl From<TleEpoch> for DateTime<Utc> {
+    fn from(epoch: TleEpoch) -> Self {
+        epoch.0
+    }
+}
+
+/// Convert a COSPAR ID such as `1998-067A` to the TLE form `98067A`.
+///
+/// Anything which is not a COSPAR ID is not representable, and is dropped.
+fn international_designator(object_id: &str) -> String {
+    match object_id.split_once('-') {
+        Some((year, rest)) if year.len() == 4 && (4..=6).contains(&rest.len()) => {
+            format!("{}{}", &year[2..], rest)
+        }
+        _ => String::new(),
+    }
+}
+
+/// Convert a TLE international designator such as `98067A` to the COSPAR form `1998-067A`.
+fn object_id(designator: &str) -> Option<String> {
+    let year: i32 = designator.get(..2)?.parse().ok()?;
+    let year = if year < 57 { year + 2000 } else { year + 1900 };
+    let rest = designator.get(2..).filter(|rest| rest.len() >= 4)?;
+    Some(format!("{}-{}", year, rest))
+}
+
+/// Compute the modulo-10 checksum of the first 68 columns of a TLE line.
+///
+/// Digits count as their value and minus signs count as one; everything else is ignored.
+pub(crate) fn checksum(line: &str) -> u32 {
+    line.chars().take(68).fold(0, |acc, c| {
+        acc + match c {
+            '-' => 1,
+            c if c.is_ascii_digit() => c.to_digit(10).unwrap(),
+            _ => 0,
+        }
+    }) % 10
+}
+
+/// Append the checksum to a 68 column TLE line.
+pub(crate) fn add_checksum(mut line: String) -> String {
+    let checksum = checksum(&line);
+    line.push_str(&checksum.to_string());
+    line
+}
+
+/// One line of a TLE, with its line number for error reporting.
+#[derive(Clone, Copy)]
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Check the length and leading line number of a TLE line.
+    fn new(number: usize, text: &'a str) -> Result<Line<'a>> {
+        if text.len() != TLE_LINE_LENGTH {
+            return Err(TleParseError::WrongLength {
+                line: number,
+                expected: TLE_LINE_LENGTH,
+                actual: text.len(),
+            }
+            .into());
+        }
+
+        let found = text.chars().next().unwrap_or(' ');
+        if found.to_digit(10) != Some(number as u32) {
+            return Err(TleParseError::WrongLineNumber {
+                line: number,
+                found,
+            }
+            .into());
+        }
+
+        Ok(Line { number, text })
+    }
+
+    fn invalid(&self, field: &'static str, columns: Range<usize>) -> Error {
+        TleParseError::InvalidField {
+            line: self.number,
+            field,
+            columns,
+        }
+        .into()
+    }
+
+    fn field(&self, columns: Range<usize>, name: &'static str) -> Result<&'a str> {
+        self.text
+            .get(columns.clone())
+            .filter(|f| f.is_ascii())
+            .ok_or_else(|| self.invalid(name, columns))
+    }
+
+    fn catalog_number(&self) -> Result<u32> {
+        let name = "catalog number";
+        decode_catalog_number(self.field(CATALOG_NUMBER_COLUMNS, name)?)
+            .ok_or_else(|| self.invalid(name, CATALOG_NUMBER_COLUMNS))
+    }
+
+    /// Parse a plain decimal field. Blank fields are read as zero, as in the reference parser.
+    fn decimal(&self, columns: Range<usize>, name: &'static str) -> Result<f64> {
+        let value = self.field(columns.clone(), name)?.trim();
+        if value.is_empty() {
+            return Ok(0.0);
+        }
+        value.parse().map_err(|_| self.invalid(name, columns))
+    }
+
+    fn integer<T: std::str::FromStr + Default>(
+        &self,
+        columns: Range<usize>,
+        name: &'static str,
+    ) -> Result<T> {
+        let value = self.field(columns.clone(), name)?.trim();
+        if value.is_empty() {
+            return Ok(T::default());
+        }
+        value.parse().map_err(|_| self.invalid(name, columns))
+    }
+
+    fn exponential(&self, columns: Range<usize>, name: &'static str) -> Result<f64> {
+        parse_exponential(self.field(columns.clone(), name)?)
+            .ok_or_else(|| self.invalid(name, columns))
+    }
+
+    fn epoch(&self) -> Result<DateTime<Utc>> {
+        let columns = 18..32;
+        parse_epoch(self.field(columns.clone(), "epoch")?)
+            .ok_or_else(|| self.invalid("epoch", columns))
+    }
+}
+
+/// Parse a field in the TLE exponential notation, e.g. ` 38778-4` for 0.38778e-4.
+fn parse_exponential(value: &str) -> Option<f64> {
+    let sign = match &value[..1] {
+        "-" => -1.0,
+        " " | "+" => 1.0,
+        _ => return None,
+    };
+    let mantissa: f64 = format!("0.{}", value[1..6].replace(' ', "0"))
+        .parse()
+        .ok()?;
+    let exponent: i32 = value[6..].replace(' ', "").parse().ok()?;
+    Some(sign * mantissa * 10f64.powi(exponent))
+}
+
+/// Parse the epoch in columns 19-32, given as a two digit year and fractional day of year.
+fn parse_epoch(epoch: &str) -> Option<DateTime<Utc>> {
+    let year: i32 = epoch[..2].trim().parse().ok()?;
+    let year = if year < 57 { year + 2000 } else { year + 1900 };
+
+    let (day, fraction) = epoch[2..]
+        .trim()
+        .split_once('.')
+        .unwrap_or((epoch[2..].trim(), ""));
+    let day: u32 = day.parse().ok()?;
+    let fraction_digits = fraction.len() as u32;
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().ok()?
+    };
+
+    // Integer arithmetic keeps the eight decimal places of the day exact to the nanosecond.
+    let scale = 10u128.checked_pow(fraction_digits)?;
+    let nanoseconds = (fraction as u128 * NANOSECONDS_PER_DAY as u128 + scale / 2) / scale;
+
+    // The day must fall within the year, rather than running on into the next.
+    let date = NaiveDate::from_yo_opt(year, day)?;
+    Some(date.and_hms_opt(0, 0, 0).unwrap().and_utc() + Duration::nanoseconds(nanoseconds as i64))
+}
+
+/// Check that a formatted field fills exactly the columns it is written to.
+fn fit(text: String, width: usize, name: &str) -> Result<String> {
+    if text.len() == width {
+        Ok(text)
+    } else {
+        Err(Error::UnrepresentableTwoLineElement(format!(
+            "The {} {} does not fit in its {} columns",
+            name,
+            text.trim(),
+            width
+        )))
+    }
+}
+
+/// Format a value in columns 34-43 as a sign followed by eight decimal places, e.g. ` .00001715`.
+fn format_decimal(value: f64) -> String {
+    let sign = if value.is_sign_negative() { '-' } else { ' ' };
+    let digits = format!("{:.8}", value.abs());
+    format!("{}{}", sign, digits.strip_prefix('0').unwrap_or(&digits))
+}
+
+/// Format a value in the TLE exponential notation, with an implied leading decimal point.
+fn format_exponential(value: f64, zero_exponent_sign: char) -> String {
+    let sign = if value.is_sign_negative() { '-' } else { ' ' };
+    let magnitude = value.abs();
+    if magnitude == 0.0 {
+        return format!("{}00000{}0", sign, zero_exponent_sign);
+    }
+
+    // Values below the smallest exponent lose leading digits of their mantissa instead. A value
+    // whose exponent is too large gives a longer field, which the caller rejects.
+    let mut exponent = (magnitude.log10().floor() as i32 + 1).max(-9);
+    let mut mantissa = (magnitude / 10f64.powi(exponent) * 1e5).round() as u32;
+    if mantissa >= 100_000 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    if mantissa == 0 {
+        return format!("{}00000{}0", sign, zero_exponent_sign);
+    }
+
+    let exponent_sign = match exponent {
+        0 => zero_exponent_sign,
+        e if e < 0 => '-',
+        _ => '+',
+    };
+    format!("{}{:05}{}{}", sign, mantissa, exponent_sign, exponent.abs())
+}
+
+/// Decode a five character catalog number field, which may use the Alpha-5 scheme.
+///
+/// Alpha-5 replaces the leading digit of numbers of 100000 and above with a letter, skipping `I`
+/// and `O` to avoid confusion with digits, so that `A0001` is 100001 and `Z9999` is 339999.
+pub(crate) fn decode_catalog_number(field: &str) -> Option<u32> {
+    let field = field.trim();
+    let first = field.chars().next()?;
+
+    if first.is_ascii_digit() {
+        return field.parse().ok();
+    }
+
+    let rest = &field[first.len_utf8()..];
+    if rest.len() != 4 || !rest.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let leading = match first {
+        'A'..='H' => first as u32 - 'A' as u32 + 10,
+        'J'..='N' => first as u32 - 'J' as u32 + 18,
+        'P'..='Z' => first as u32 - 'P' as u32 + 23,
+        _ => return None,
+    };
+
+    Some(leading * 10_000 + rest.parse::<u32>().ok()?)
+}
+
+/// Encode a catalog number into a five character field, using Alpha-5 where required.
+///
+/// Returns `None` if the number is too large to be represented in a TLE.
+pub(crate) fn encode_catalog_number(catalog_number: u32) -> Option<String> {
+    if catalog_number < 100_000 {
+        return Some(format!("{:05}", catalog_number));
+    }
+    if catalog_number > MAX_ALPHA5_CATALOG_NUMBER {
+        return None;
+    }
+
+    let leading = catalog_number / 10_000;
+    let letter = match leading {
+        10..=17 => b'A' + (leading - 10) as u8,
+        18..=22 => b'J' + (leading - 18) as u8,
+        _ => b'P' + (leading - 23) as u8,
+    };
+
+    Some(format!("{}{:04}", letter as char, catalog_number % 10_000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::read_to_string;
+
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_fields_round_trip() -> Result<()> {
+        let catalog = read_to_string("test_data/spire.txt").unwrap();
+        let lines: Vec<_> = catalog
+            .lines()
+            .filter(|l| l.starts_with("1 ") || l.starts_with("2 "))
+            .collect();
+
+        for pair in lines.chunks(2) {
+            let fields = TleFields::parse(pair[0], pair[1])?;
+            assert_eq!(fields.format()?, [pair[0], pair[1]]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let parse_error = |line1: &str, line2: &str| match TleFields::parse(line1, line2) {
+            Err(Error::MalformedTwoLineElement(e)) => e,
+            other => panic!("Expected a parse error, got {:?}", other),
+        };
+
+        assert_eq!(
+            parse_error(line1, &line2[..68]),
+            TleParseError::WrongLength {
+                line: 2,
+                expected: 69,
+                actual: 68
+            }
+        );
+        assert_eq!(
+            parse_error(line2, line2),
+            TleParseError::WrongLineNumber {
+                line: 1,
+                found: '2'
+            }
+        );
+        assert_eq!(
+            parse_error(line1, &line2.replace(" 51.6435", " 51.64X5")),
+            TleParseError::InvalidField {
+                line: 2,
+                field: "inclination",
+                columns: 8..16
+            }
+        );
+        assert_eq!(
+            parse_error(line1, &line2.replace("25544", "25545")),
+            TleParseError::CatalogNumberMismatch {
+                line1: 25544,
+                line2: 25545
+            }
+        );
+        assert_eq!(
+            parse_error(&line1.replace("20148.", "20X48."), line2).to_string(),
+            "Invalid epoch in line 1, columns 19-32"
+        );
+    }
+
+    #[test]
+    fn test_normalize_line() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+
+        assert_eq!(
+            normalize_line(&format!("{}\r\n", line1), 1, ParseMode::Strict)?,
+            line1
+        );
+        assert_eq!(
+            normalize_line(&format!("\t{} \t", &line1[..68]), 1, ParseMode::Lenient)?,
+            line1
+        );
+        assert_eq!(
+            normalize_line(&format!(" {} ", line1), 1, ParseMode::Strict)?,
+            line1
+        );
+        assert_eq!(
+            normalize_line(&format!("{}0", line1), 1, ParseMode::Strict),
+            Err(Error::MalformedTwoLineElement(TleParseError::WrongLength {
+                line: 1,
+                expected: 69,
+                actual: 70
+            }))
+        );
+
+        // A multi-byte final character is reported rather than split.
+        let accented = format!("{}é", &line1[..67]);
+        assert_eq!(accented.len(), 69);
+        assert_eq!(
+            normalize_line(&accented, 1, ParseMode::Strict),
+            Err(Error::MalformedTwoLineElement(
+                TleParseError::InvalidField {
+                    line: 1,
+                    field: "checksum",
+                    columns: 68..69
+                }
+            ))
+        );
+
+        let corrupted = line1.replace("21301450", "21301451");
+        assert_eq!(
+            normalize_line(&corrupted, 1, ParseMode::Strict),
+            Err(Error::MalformedTwoLineElement(
+                TleParseError::ChecksumMismatch {
+                    line: 1,
+                    expected: 3,
+                    actual: 2
+                }
+            ))
+        );
+        assert_eq!(
+            normalize_line(&corrupted, 1, ParseMode::Lenient)?,
+            corrupted
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_exponential_notation() {
+        assert_eq!(parse_exponential(" 38778-4"), Some(0.38778e-4));
+        assert_eq!(parse_exponential("-11606-4"), Some(-0.11606e-4));
+        assert_eq!(parse_exponential(" 12340-4"), Some(0.1234e-4));
+        assert_eq!(parse_exponential(" 00000+0"), Some(0.0));
+        assert_eq!(parse_exponential(" 3877X-4"), None);
+
+        assert_eq!(format_exponential(0.38778e-4, '-'), " 38778-4");
+        assert_eq!(format_exponential(-0.11606e-4, '-'), "-11606-4");
+        assert_eq!(format_exponential(1e-4, '-'), " 10000-3");
+        assert_eq!(format_exponential(0.0, '+'), " 00000+0");
+        assert_eq!(format_exponential(0.0, '-'), " 00000-0");
+        assert_eq!(format_exponential(1e-11, '-'), " 01000-9");
+        assert_eq!(format_exponential(1e-16, '-'), " 00000-0");
+    }
+
+    #[test]
+    fn test_unrepresentable_fields() -> Result<()> {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        let fields = TleFields::parse(line1, line2)?;
+        let unrepresentable = |fields: TleFields| {
+            matches!(
+                fields.format(),
+                Err(Error::UnrepresentableTwoLineElement(_))
+            )
+        };
+
+        assert!(unrepresentable(TleFields {
+            mean_motion: 100.0,
+            ..fields.clone()
+        }));
+        assert!(unrepresentable(TleFields {
+            mean_motion: 99.999999999,
+            ..fields.clone()
+        }));
+        assert!(unrepresentable(TleFields {
+            mean_motion_dot: -1.0,
+            ..fields.clone()
+        }));
+        // The mantissa rounds up to 100000, which carries into an exponent of 10.
+        assert!(unrepresentable(TleFields {
+            bstar: 0.999_999e9,
+            ..fields.clone()
+        }));
+        assert!(unrepresentable(TleFields {
+            mean_motion_ddot: 1e10,
+            ..fields.clone()
+        }));
+        assert!(unrepresentable(TleFields {
+            eccentricity: 1.0,
+            ..fields.clone()
+        }));
+        assert!(unrepresentable(TleFields {
+            inclination: -100.0,
+            ..fields.clone()
+        }));
+
+        // The largest values which fit are written in full.
+        let largest = TleFields {
+            mean_motion: 99.99999999,
+            bstar: 0.99999e9,
+            mean_motion_ddot: -1e-11,
+            ..fields
+        };
+        let [line1, line2] = largest.format()?;
+        assert_eq!(&line1[44..61], "-01000-9  99999+9");
+        assert_eq!(&line2[52..63], "99.99999999");
+        Ok(())
+    }
+
+    #[test]
+    fn test_tle_epoch() -> Result<()> {
+        let epoch: TleEpoch = "20148.21301450".parse()?;
+        assert_eq!(
+            epoch.0,
+            Utc.with_ymd_and_hms(2020, 5, 27, 5, 6, 44).unwrap() + Duration::microseconds(452_800)
+        );
+        assert_eq!(epoch.to_string(), "20148.21301450");
+
+        let epoch: TleEpoch = "98001".parse()?;
+        assert_eq!(epoch.0, Utc.with_ymd_and_hms(1998, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(epoch.to_string(), "98001.00000000");
+
+        let end_of_year = Utc.with_ymd_and_hms(2020, 12, 31, 23, 59, 59).unwrap()
+            + Duration::microseconds(999_900);
+        assert_eq!(TleEpoch(end_of_year).to_string(), "21001.00000000");
+
+        assert!("2014X.5".parse::<TleEpoch>().is_err());
+        assert!("".parse::<TleEpoch>().is_err());
+
+        // Day numbers outside the year are rejected, and long fractions do not overflow.
+        assert!("20000.5".parse::<TleEpoch>().is_err());
+        assert!("20367.5".parse::<TleEpoch>().is_err());
+        assert!("21366.5".parse::<TleEpoch>().is_err());
+        assert_eq!(
+            "20366.5".parse::<TleEpoch>()?.0,
+            Utc.with_ymd_and_hms(2020, 12, 31, 12, 0, 0).unwrap()
+        );
+        let long = format!("24001.{}1", "0".repeat(40));
+        assert!(long.parse::<TleEpoch>().is_err());
+        let long = format!("24001.5{}", "0".repeat(17));
+        assert_eq!(
+            long.parse::<TleEpoch>()?.0,
+            Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_international_designator() {
+        assert_eq!(international_designator("1998-067A"), "98067A");
+        assert_eq!(international_designator("2014-033AL"), "14033AL");
+        assert_eq!(international_designator("UNKNOWN"), "");
+
+        assert_eq!(object_id("98067A").as_deref(), Some("1998-067A"));
+        assert_eq!(object_id("14033AL").as_deref(), Some("2014-033AL"));
+        assert_eq!(object_id(""), None);
+    }
+
+    #[test]
+    fn test_checksum() {
+        let line1 = "1 25544U 98067A   20148.21301450  .00001715  00000-0  38778-4 0  9992";
+        let line2 = "2 25544  51.6435  92.2789 0002570 358.0648 144.9972 15.49396855228767";
+        assert_eq!(checksum(line1), 2);
+        assert_eq!(checksum(line2), 7);
+    }
+
+    #[test]
+    fn test_decode_catalog_number() {
+        assert_eq!(decode_catalog_number("25544"), Some(25544));
+        assert_eq!(decode_catalog_number("00005"), Some(5));
+        assert_eq!(decode_catalog_number("A0000"), Some(100_000));
+        assert_eq!(decode_catalog_number("H9999"), Some(179_999));
+        assert_eq!(decode_catalog_number("J0000"), Some(180_000));
+        assert_eq!(decode_catalog_number("P0000"), Some(230_000));
+        assert_eq!(decode_catalog_number("Z9999"), Some(339_999));
+        assert_eq!(decode_catalog_number("I0000"), None);
+        assert_eq!(decode_catalog_number("O0000"), None);
+        assert_eq!(decode_catalog_number("a0000"), None);
+        assert_eq!(decode_catalog_number("A00X0"), None);
+    }
+
+    #[test]
+    fn test_catalog_number_round_trip() {
+        for n in [
+            0, 25544, 99_999, 100_000, 100_001, 179_999, 180_000, 229_999, 339_999,
+        ] {
+            let encoded = encode_catalog_number(n).unwrap();
+            assert_eq!(encoded.len(), 5);
+            assert_eq!(decode_catalog_number(&encoded), Some(n));
+        }
+        assert_eq!(encode_catalog_number(340_000), None);
+    }
+}
@@ -5,7 +5,7 @@ use argmin::{
     },
     solver::neldermead::NelderMead,
 };
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono::{DateTime, Datelike, Utc};
 use uom::{
     si::{
         angle::{self, degree},
@@ -15,26 +15,45 @@ use uom::{
     ConstZero,
 };
 
-use crate::{sgp4_sys, ClassicalOrbitalElements, Error, Result, StateVector, TwoLineElement};
+use crate::{
+    sgp4_sys, tle_format, ClassicalOrbitalElements, Error, PropagationOptions, Result, StateVector,
+    TleEpoch, TwoLineElement,
+};
 
 const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
 
 impl ClassicalOrbitalElements {
     pub fn as_tle_at(&self, catalog_num: u8, epoch: DateTime<Utc>) -> String {
+        self.as_tle_at_with_options(catalog_num.into(), epoch, PropagationOptions::default())
+            .expect("a catalog number below 256 fits in a TLE")
+    }
+
+    /// Format the elements as a TLE, deriving its mean motion with the options' gravity model.
+    ///
+    /// Catalog numbers of 100000 and above are written in the Alpha-5 scheme, and an error is
+    /// returned for those too large for it.
+    pub fn as_tle_at_with_options(
+        &self,
+        catalog_num: u32,
+        epoch: DateTime<Utc>,
+        options: PropagationOptions,
+    ) -> Result<String> {
+        let catalog_num = catalog_number_field(catalog_num)?;
         let tle = format!(
             "{}\n{}",
-            tle_line_1(catalog_num, epoch),
+            tle_line_1(&catalog_num, epoch),
             tle_line_2(
-                catalog_num,
+                &catalog_num,
                 self.inclination,
                 self.raan,
                 self.eccentricity,
                 self.argument_of_perigee,
                 self.mean_anomaly,
-                self.semimajor_axis
+                self.semimajor_axis,
+                options
             )
         );
-        tle
+        Ok(tle)
     }
 }
 
@@ -52,6 +71,22 @@ impl StateVector {
     /// exactly match those of the original element set. This function should not be used for
     /// production applications.
     pub fn as_tle_at(&self, catalog_num: u8, epoch: DateTime<Utc>) -> Result<String> {
+        self.as_tle_at_with_options(catalog_num.into(), epoch, PropagationOptions::default())
+    }
+
+    /// Find a TLE string that propagates to the state vector at a given epoch when used with the
+    /// given options.
+    ///
+    /// The same simplifications apply as for [StateVector::as_tle_at]. Catalog numbers of 100000
+    /// and above are written in the Alpha-5 scheme.
+    pub fn as_tle_at_with_options(
+        &self,
+        catalog_num: u32,
+        epoch: DateTime<Utc>,
+        options: PropagationOptions,
+    ) -> Result<String> {
+        let catalog_num = catalog_number_field(catalog_num)?;
+
         // The orbital elements associated with the state vector are osculating/instantaneous
         // whereas the TLE must be based on mean elements. To find a TLE which propagates to the
         // required cartesian state vector we use a numerical optimization approach, based on the
@@ -60,8 +95,9 @@ impl StateVector {
         // the error against the target position / velocity using sum-of-squares.
         let cost = FindTleProblem {
             epoch,
-            position: self.position,
-            velocity: self.velocity,
+            position: self.position.into(),
+            velocity: self.velocity.into(),
+            options,
         };
         let init_param: Vec<f64> = vec![
             self.coe.inclination.get::<degree>(),
@@ -99,8 +135,8 @@ impl StateVector {
                 let best_param = opt_res.state().best_param.as_ref().unwrap();
                 let tle = format!(
                     "{}\n{}",
-                    tle_line_1(catalog_num, epoch),
-                    params_to_tle_line2(catalog_num, best_param)
+                    tle_line_1(&catalog_num, epoch),
+                    params_to_tle_line2(&catalog_num, best_param, options)
                 );
                 Ok(tle)
             }
@@ -109,31 +145,40 @@ impl StateVector {
     }
 }
 
-fn tle_line_1(catalog_num: u8, epoch: DateTime<Utc>) -> String {
+/// Format a catalog number for columns 3-7 of a TLE, using Alpha-5 where required.
+fn catalog_number_field(catalog_num: u32) -> Result<String> {
+    tle_format::encode_catalog_number(catalog_num).ok_or_else(|| {
+        Error::UnrepresentableTwoLineElement(format!(
+            "Catalog number {} is too large for a TLE, the maximum is {}",
+            catalog_num,
+            tle_format::MAX_ALPHA5_CATALOG_NUMBER
+        ))
+    })
+}
+
+fn tle_line_1(catalog_num: &str, epoch: DateTime<Utc>) -> String {
     let epoch_year = epoch.year() % 100;
-    let epoch_day = epoch.ordinal();
-    let epoch_day_fraction = epoch.num_seconds_from_midnight() as f64 / SECONDS_PER_DAY;
-    let epoch_day_fraction_int = (epoch_day_fraction * 100000000.0).round() as i64;
     let line = format!(
-        "1 {0:05}U {1:2}001A   {1:2}{2:03}.{3:08}  .00000000  00000-0  00000-0 0  999",
-        // |-----| |---------| |---||---| |-----| |--------| |------| |------| ^ |--|
-        // 3-8     10-17       19      23 25-32   34-43      45-52    54-61      65 68
+        "1 {0:>5}U {1:2}001A   {2}  .00000000  00000-0  00000-0 0  999",
+        // |-----| |---------| |------------| |--------| |------| |------| ^ |--|
+        // 3-8     10-17       19-32          34-43      45-52    54-61      65 68
         catalog_num,
         epoch_year,
-        epoch_day,
-        epoch_day_fraction_int
+        TleEpoch(epoch)
     );
-    add_tle_checksum(line)
+    tle_format::add_checksum(line)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn tle_line_2(
-    catalog_num: u8,
+    catalog_num: &str,
     inclination: Angle,
     raan: Angle,
     eccentricity: f64,
     argument_of_perigee: Angle,
     mean_anomaly: Angle,
     semimajor_axis: Length,
+    options: PropagationOptions,
 ) -> String {
     use std::f64::consts::PI;
 
@@ -142,11 +187,11 @@ fn tle_line_2(
     let ecc_int = (eccentricity * 10e6).round() as i64;
     let argp = normalize_angle(argument_of_perigee).get::<angle::degree>();
     let ma = normalize_angle(mean_anomaly).get::<angle::degree>();
-    let consts = sgp4_sys::gravitational_constants();
+    let consts = sgp4_sys::gravitational_constants(options.gravity_model.into());
     let mm = SECONDS_PER_DAY
         / ((2.0 * PI) * (semimajor_axis.get::<kilometer>().powi(3) / consts.mu).sqrt());
     let line = format!(
-        "2 {0:05} {1:>8.4} {2:>8.4} {3:07} {4:>8.4} {5:>8.4} {6:>11.8}00001",
+        "2 {0:>5} {1:>8.4} {2:>8.4} {3:07} {4:>8.4} {5:>8.4} {6:>11.8}00001",
         // |----| |------| |------| |----| |------| |------| |-------||---|
         // 3-7    9-16     18-25    27-33  35-42    44-51    53-63    64-68
         catalog_num,
@@ -157,28 +202,17 @@ fn tle_line_2(
         ma,
         mm
     );
-    add_tle_checksum(line)
-}
-
-fn add_tle_checksum(mut line: String) -> String {
-    let checksum = line.chars().fold(0, |acc, c| {
-        acc + match c {
-            '-' => 1,
-            c if c.is_ascii_digit() => c.to_digit(10).unwrap(),
-            _ => 0,
-        }
-    }) % 10;
-    line.push_str(&checksum.to_string());
-    line
+    tle_format::add_checksum(line)
 }
 
 struct FindTleProblem {
     pub epoch: DateTime<Utc>,
     pub position: [f64; 3],
     pub velocity: [f64; 3],
+    pub options: PropagationOptions,
 }
 
-fn params_to_tle_line2(catalog_num: u8, param: &[f64]) -> String {
+fn params_to_tle_line2(catalog_num: &str, param: &[f64], options: PropagationOptions) -> String {
     let inclination = Angle::new::<degree>(param[0]);
     let raan = Angle::new::<degree>(param[1]);
     let eccentricity = param[2];
@@ -194,6 +228,7 @@ fn params_to_tle_line2(catalog_num: u8, param: &[f64]) -> String {
         normalize_angle(argument_of_perigee),
         normalize_angle(mean_anomaly),
         semimajor_axis.max(Length::ZERO),
+        options,
     )
 }
 
@@ -217,11 +252,11 @@ impl CostFunction for FindTleProblem {
     type Output = f64;
 
     fn cost(&self, param: &Self::Param) -> std::result::Result<f64, argmin::core::Error> {
-        let catalog_num = 1;
+        let catalog_num = "00001";
 
         let tle_line_1 = tle_line_1(catalog_num, self.epoch);
-        let tle_line_2 = params_to_tle_line2(catalog_num, param);
-        let tle = TwoLineElement::new(&tle_line_1, &tle_line_2)?;
+        let tle_line_2 = params_to_tle_line2(catalog_num, param, self.options);
+        let tle = TwoLineElement::new_with_options(&tle_line_1, &tle_line_2, self.options)?;
         let prop_sv = tle.propagate_to(self.epoch)?;
 
         let error = (self.position[0] - prop_sv.position[0]).powi(2)
@@ -273,6 +308,124 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_roundtrip_with_options() -> Result<()> {
+        use crate::{GravityModel, OperationMode};
+        use float_cmp::assert_approx_eq;
+
+        let options = PropagationOptions {
+            gravity_model: GravityModel::Wgs72,
+            operation_mode: OperationMode::Afspc,
+        };
+        let epoch = Utc.with_ymd_and_hms(2021, 5, 25, 0, 0, 0).unwrap();
+        let line1 = "1 00000U 21001A   21145.00000000  .00000000  00000-0  00000-0 0  9997";
+        let line2 = "2 00000  36.9006 237.1418 0013279   1.4043 318.6732 14.97334669000019";
+        let svector =
+            TwoLineElement::new_with_options(line1, line2, options)?.propagate_to(epoch)?;
+
+        let tle = svector.as_tle_at_with_options(0, epoch, options)?;
+        let lines: Vec<_> = tle.lines().collect();
+        let svector_2 =
+            TwoLineElement::new_with_options(lines[0], lines[1], options)?.propagate_to(epoch)?;
+        for i in 0..3 {
+            assert_approx_eq!(
+                f64,
+                svector.position[i],
+                svector_2.position[i],
+                epsilon = 0.01
+            );
+            assert_approx_eq!(
+                f64,
+                svector.velocity[i],
+                svector_2.velocity[i],
+                epsilon = 0.01
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tle_from_ecef_fix() -> Result<()> {
+        use crate::EarthOrientationParameters;
+        use float_cmp::assert_approx_eq;
+
+        let epoch = Utc.with_ymd_and_hms(2021, 5, 25, 0, 0, 0).unwrap();
+        let eop = EarthOrientationParameters::default();
+        let line1 = "1 00000U 21001A   21145.00000000  .00000000  00000-0  00000-0 0  9997";
+        let line2 = "2 00000  36.9006 237.1418 0013279   1.4043 318.6732 14.97334669000019";
+        let fix = TwoLineElement::new(line1, line2)?
+            .propagate_to(epoch)?
+            .to_ecef(&eop);
+
+        let tle = fix.to_teme(&eop).as_tle_at(0, epoch)?;
+        let fix_2 = TwoLineElement::from_lines(&tle)?
+            .propagate_to(epoch)?
+            .to_ecef(&eop);
+        for i in 0..3 {
+            assert_approx_eq!(f64, fix.position[i], fix_2.position[i], epsilon = 0.01);
+            assert_approx_eq!(f64, fix.velocity[i], fix_2.velocity[i], epsilon = 0.01);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tle_from_quantities() -> Result<()> {
+        use float_cmp::assert_approx_eq;
+        use uom::si::{f64::Velocity, length::meter, velocity::meter_per_second};
+
+        let epoch = Utc.with_ymd_and_hms(2021, 5, 25, 0, 0, 0).unwrap();
+        let position = [-3_767_078.3, -5_832_374.7, 13.4].map(Length::new::<meter>);
+        let velocity = [5087.84, -3285.89, 4561.43].map(Velocity::new::<meter_per_second>);
+        let svector = StateVector::from_quantities(epoch, position, velocity);
+
+        let tle = svector.as_tle_at(0, epoch)?;
+        let svector_2 = TwoLineElement::from_lines(&tle)?.propagate_to(epoch)?;
+        let position_2 = svector_2.position_quantities();
+        for i in 0..3 {
+            assert_approx_eq!(
+                f64,
+                position[i].get::<meter>(),
+                position_2[i].get::<meter>(),
+                epsilon = 10.0
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_alpha5_catalog_number() -> Result<()> {
+        let epoch = Utc.with_ymd_and_hms(2021, 5, 25, 0, 0, 0).unwrap();
+        let tle_1 = "1 00000U 21001A   21145.00000000  .00000000  00000-0  00000-0 0  9997\n2 00000  36.9006 237.1418 0013279   1.4043 318.6732 14.97334669000019";
+        let coe: ClassicalOrbitalElements = TwoLineElement::from_lines(tle_1)?
+            .propagate_to(epoch)?
+            .into();
+
+        let options = PropagationOptions::default();
+        let tle_2 = coe.as_tle_at_with_options(270_001, epoch, options)?;
+        assert_eq!(
+            TwoLineElement::from_lines(&tle_2)?.catalog_number(),
+            270_001
+        );
+        assert!(coe.as_tle_at_with_options(340_000, epoch, options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sub_second_epoch() -> Result<()> {
+        let tle_1 = "1 00000U 21001A   21145.00000000  .00000000  00000-0  00000-0 0  9997\n2 00000  36.9006 237.1418 0013279   1.4043 318.6732 14.97334669000019";
+        let epoch = Utc.with_ymd_and_hms(2021, 5, 25, 12, 34, 56).unwrap()
+            + chrono::Duration::milliseconds(789);
+        let coe: ClassicalOrbitalElements = TwoLineElement::from_lines(tle_1)?
+            .propagate_to(epoch)?
+            .into();
+
+        // The epoch is kept to the 1e-8 day resolution of the format.
+        let tle_2 = TwoLineElement::from_lines(&coe.as_tle_at(0, epoch))?;
+        let error = tle_2.epoch()? - epoch;
+        assert!(error.abs() < chrono::Duration::milliseconds(1));
+        Ok(())
+    }
+
     #[test]
     fn test_roundtrip_tle_to_tle() -> Result<()> {
         let epoch = Utc.with_ymd_and_hms(2021, 5, 25, 0, 0, 0).unwrap();